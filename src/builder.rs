@@ -0,0 +1,345 @@
+use crate::open_gal::OGalParse;
+use crate::{table, Chip, Error, ErrorKind, LookupTable, Signal, Span};
+
+/// builds a `Chip` and checks it with `build`
+#[derive(Debug, Clone)]
+pub struct ChipBuilder<T> {
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    parts: Vec<T>,
+    builtin: Option<String>,
+    clocked: Vec<String>,
+    span: Span,
+}
+
+impl<T> ChipBuilder<T> {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            parts: Vec::new(),
+            builtin: None,
+            clocked: Vec::new(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn input(mut self, name: &str) -> Self {
+        self.inputs.push(Signal::new(name));
+        self
+    }
+
+    pub fn inputs(mut self, names: &[&str]) -> Self {
+        self.inputs.extend(names.iter().map(|&s| Signal::new(s)));
+        self
+    }
+
+    /// an input bus like `Signal::bus("a", 16)`
+    pub fn input_signal(mut self, signal: Signal) -> Self {
+        self.inputs.push(signal);
+        self
+    }
+
+    pub fn output(mut self, name: &str) -> Self {
+        self.outputs.push(Signal::new(name));
+        self
+    }
+
+    pub fn outputs(mut self, names: &[&str]) -> Self {
+        self.outputs.extend(names.iter().map(|&s| Signal::new(s)));
+        self
+    }
+
+    pub fn output_signal(mut self, signal: Signal) -> Self {
+        self.outputs.push(signal);
+        self
+    }
+
+    pub fn part(mut self, part: T) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    pub fn parts(mut self, parts: Vec<T>) -> Self {
+        self.parts.extend(parts);
+        self
+    }
+
+    pub fn builtin(mut self, name: &str) -> Self {
+        self.builtin = Some(name.to_string());
+        self
+    }
+
+    pub fn clocked(mut self, names: &[&str]) -> Self {
+        self.clocked.extend(names.iter().map(|s| s.to_string()));
+        self
+    }
+
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// checks the names, that no pin is declared twice, that every clocked pin exists
+    /// and that a builtin chip has no parts
+    pub fn build(self) -> Result<Chip<T>, Error> {
+        check_name(&self.name)?;
+        if let Some(builtin) = &self.builtin {
+            check_name(builtin)?;
+            if !self.parts.is_empty() {
+                return Err(Error::msg(
+                    ErrorKind::BuiltinWithParts,
+                    &format!("builtin chip <{}> can not have parts", self.name),
+                ));
+            }
+        }
+        check_pins(self.inputs.iter().chain(&self.outputs).map(Signal::name))?;
+        for name in &self.clocked {
+            if !self
+                .inputs
+                .iter()
+                .chain(&self.outputs)
+                .any(|pin| pin.name() == name)
+            {
+                return Err(unknown_signal(name));
+            }
+        }
+
+        Ok(Chip {
+            name: self.name,
+            inputs: self.inputs,
+            outputs: self.outputs,
+            parts: self.parts,
+            builtin: self.builtin,
+            clocked: self.clocked,
+            span: self.span,
+        })
+    }
+}
+
+/// builds a `LookupTable` and checks it with `build`
+#[derive(Debug, Clone)]
+pub struct LookupTableBuilder {
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    table: Vec<Vec<bool>>,
+    span: Span,
+}
+
+impl LookupTableBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            table: Vec::new(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn input(mut self, name: &str) -> Self {
+        self.inputs.push(Signal::new(name));
+        self
+    }
+
+    pub fn inputs(mut self, names: &[&str]) -> Self {
+        self.inputs.extend(names.iter().map(|&s| Signal::new(s)));
+        self
+    }
+
+    /// every bit of the bus is its own input
+    pub fn input_signal(mut self, signal: Signal) -> Self {
+        self.inputs.extend(signal.bits());
+        self
+    }
+
+    pub fn output(mut self, name: &str) -> Self {
+        self.outputs.push(Signal::new(name));
+        self
+    }
+
+    pub fn outputs(mut self, names: &[&str]) -> Self {
+        self.outputs.extend(names.iter().map(|&s| Signal::new(s)));
+        self
+    }
+
+    pub fn output_signal(mut self, signal: Signal) -> Self {
+        self.outputs.extend(signal.bits());
+        self
+    }
+
+    /// one column for every output, the row index has the first input as its highest bit
+    pub fn table(mut self, table: Vec<Vec<bool>>) -> Self {
+        self.table = table;
+        self
+    }
+
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// checks the names, that no pin is declared twice and that the table has one
+    /// column with `2^inputs` rows for every output, the name can be empty like the
+    /// tables of open_gal
+    pub fn build(self) -> Result<LookupTable, Error> {
+        if !self.name.is_empty() {
+            check_name(&self.name)?;
+        }
+        check_bits(self.inputs.iter().chain(&self.outputs))?;
+        table::check_size(self.inputs.len(), self.outputs.len())?;
+
+        let rows = 1 << self.inputs.len();
+        if self.table.len() != self.outputs.len()
+            || self.table.iter().any(|column| column.len() != rows)
+        {
+            return Err(Error::msg(
+                ErrorKind::InvalidTable,
+                &format!(
+                    "table <{}> needs {} columns with {} rows",
+                    self.name,
+                    self.outputs.len(),
+                    rows
+                ),
+            ));
+        }
+
+        Ok(LookupTable {
+            name: self.name,
+            inputs: self.inputs,
+            output: self.outputs,
+            table: self.table,
+            span: self.span,
+        })
+    }
+}
+
+/// builds an `OGalParse` and checks it with `build`
+#[derive(Debug, Clone, Default)]
+pub struct OGalBuilder {
+    pin_map: Vec<(Signal, usize)>,
+    lookup_table: Vec<LookupTable>,
+    dff_enable: Vec<Signal>,
+}
+
+impl OGalBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pin(mut self, name: &str, num: usize) -> Self {
+        self.pin_map.push((Signal::new(name), num));
+        self
+    }
+
+    /// a bit of a bus like `Signal::index("i", 0)`
+    pub fn pin_signal(mut self, signal: Signal, num: usize) -> Self {
+        self.pin_map.push((signal, num));
+        self
+    }
+
+    pub fn lookup_table(mut self, lookup_table: LookupTable) -> Self {
+        self.lookup_table.push(lookup_table);
+        self
+    }
+
+    pub fn dff(mut self, name: &str) -> Self {
+        self.dff_enable.push(Signal::new(name));
+        self
+    }
+
+    pub fn dff_signal(mut self, signal: Signal) -> Self {
+        self.dff_enable.push(signal);
+        self
+    }
+
+    /// checks that every pin name and number is used once and that every flip-flop
+    /// is on a pin
+    pub fn build(self) -> Result<OGalParse, Error> {
+        check_bits(self.pin_map.iter().map(|(pin, _)| pin))?;
+        for (i, (name, num)) in self.pin_map.iter().enumerate() {
+            if let Some((old_name, _)) = self.pin_map[..i].iter().find(|pin| pin.1 == *num) {
+                return Err(Error::msg(
+                    ErrorKind::DuplicatePin,
+                    &format!(
+                        "pin <{}> = <{}> conflicts with pin <{}> = <{}>",
+                        num, name, num, old_name
+                    ),
+                ));
+            }
+        }
+        for output in &self.dff_enable {
+            if !self.pin_map.iter().any(|(pin, _)| pin == output) {
+                return Err(unknown_signal(&output.to_string()));
+            }
+        }
+
+        Ok(OGalParse::new_signals(
+            self.pin_map,
+            self.lookup_table,
+            self.dff_enable,
+        ))
+    }
+}
+
+// ----------------------------------- utils -----------------------------------
+
+/// same rule as the identifiers of the lexers
+fn check_name(name: &str) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => true,
+        Some('_' | '$') => name.len() > 1,
+        _ => false,
+    } && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if !valid {
+        return Err(Error::msg(
+            ErrorKind::InvalidName,
+            &format!("invalid name <{}>", name),
+        ));
+    }
+    Ok(())
+}
+
+fn check_pins<'a>(names: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut seen: Vec<&str> = Vec::new();
+    for name in names {
+        check_name(name)?;
+        if seen.contains(&name) {
+            return Err(Error::msg(
+                ErrorKind::DuplicatePin,
+                &format!("pin <{}> is declared more than once", name),
+            ));
+        }
+        seen.push(name);
+    }
+    Ok(())
+}
+
+/// like `check_pins` for the single bits of tables and pins, `a[0]` and `a[1]` are
+/// different bits of the same name
+fn check_bits<'a>(bits: impl Iterator<Item = &'a Signal>) -> Result<(), Error> {
+    let mut seen: Vec<&Signal> = Vec::new();
+    for bit in bits {
+        check_name(bit.name())?;
+        if seen.contains(&bit) {
+            return Err(Error::msg(
+                ErrorKind::DuplicatePin,
+                &format!("pin <{}> is declared more than once", bit),
+            ));
+        }
+        seen.push(bit);
+    }
+    Ok(())
+}
+
+fn unknown_signal(name: &str) -> Error {
+    Error::msg(
+        ErrorKind::UnknownSignal,
+        &format!("unknown signal <{}>", name),
+    )
+}
@@ -0,0 +1,314 @@
+use crate::{Chip, ComponentMap, Library, Signal};
+
+// The chips of nand2tetris that the simulator implements. Their signatures are used by
+// the checks when a project has no file for a part and the models are the behaviour of
+// the chips for a simulator.
+
+/// name, inputs and outputs with their widths and the clocked inputs of every chip
+type Definition = (
+    &'static str,
+    &'static [(&'static str, usize)],
+    &'static [(&'static str, usize)],
+    &'static [&'static str],
+);
+
+/// the width of the buses of the hack computer
+const WORD: usize = 16;
+
+const CHIPS: [Definition; 30] = [
+    ("Nand", &[("a", 1), ("b", 1)], &[("out", 1)], &[]),
+    ("Not", &[("in", 1)], &[("out", 1)], &[]),
+    ("And", &[("a", 1), ("b", 1)], &[("out", 1)], &[]),
+    ("Or", &[("a", 1), ("b", 1)], &[("out", 1)], &[]),
+    ("Xor", &[("a", 1), ("b", 1)], &[("out", 1)], &[]),
+    ("Mux", &[("a", 1), ("b", 1), ("sel", 1)], &[("out", 1)], &[]),
+    ("DMux", &[("in", 1), ("sel", 1)], &[("a", 1), ("b", 1)], &[]),
+    ("Not16", &[("in", WORD)], &[("out", WORD)], &[]),
+    ("And16", &[("a", WORD), ("b", WORD)], &[("out", WORD)], &[]),
+    ("Or16", &[("a", WORD), ("b", WORD)], &[("out", WORD)], &[]),
+    (
+        "Mux16",
+        &[("a", WORD), ("b", WORD), ("sel", 1)],
+        &[("out", WORD)],
+        &[],
+    ),
+    ("Or8Way", &[("in", 8)], &[("out", 1)], &[]),
+    (
+        "Mux4Way16",
+        &[
+            ("a", WORD),
+            ("b", WORD),
+            ("c", WORD),
+            ("d", WORD),
+            ("sel", 2),
+        ],
+        &[("out", WORD)],
+        &[],
+    ),
+    (
+        "Mux8Way16",
+        &[
+            ("a", WORD),
+            ("b", WORD),
+            ("c", WORD),
+            ("d", WORD),
+            ("e", WORD),
+            ("f", WORD),
+            ("g", WORD),
+            ("h", WORD),
+            ("sel", 3),
+        ],
+        &[("out", WORD)],
+        &[],
+    ),
+    (
+        "DMux4Way",
+        &[("in", 1), ("sel", 2)],
+        &[("a", 1), ("b", 1), ("c", 1), ("d", 1)],
+        &[],
+    ),
+    (
+        "DMux8Way",
+        &[("in", 1), ("sel", 3)],
+        &[
+            ("a", 1),
+            ("b", 1),
+            ("c", 1),
+            ("d", 1),
+            ("e", 1),
+            ("f", 1),
+            ("g", 1),
+            ("h", 1),
+        ],
+        &[],
+    ),
+    (
+        "HalfAdder",
+        &[("a", 1), ("b", 1)],
+        &[("sum", 1), ("carry", 1)],
+        &[],
+    ),
+    (
+        "FullAdder",
+        &[("a", 1), ("b", 1), ("c", 1)],
+        &[("sum", 1), ("carry", 1)],
+        &[],
+    ),
+    ("Add16", &[("a", WORD), ("b", WORD)], &[("out", WORD)], &[]),
+    ("Inc16", &[("in", WORD)], &[("out", WORD)], &[]),
+    (
+        "ALU",
+        &[
+            ("x", WORD),
+            ("y", WORD),
+            ("zx", 1),
+            ("nx", 1),
+            ("zy", 1),
+            ("ny", 1),
+            ("f", 1),
+            ("no", 1),
+        ],
+        &[("out", WORD), ("zr", 1), ("ng", 1)],
+        &[],
+    ),
+    ("DFF", &[("in", 1)], &[("out", 1)], &["in"]),
+    (
+        "Bit",
+        &[("in", 1), ("load", 1)],
+        &[("out", 1)],
+        &["in", "load"],
+    ),
+    (
+        "Register",
+        &[("in", WORD), ("load", 1)],
+        &[("out", WORD)],
+        &["in", "load"],
+    ),
+    (
+        "RAM8",
+        &[("in", WORD), ("load", 1), ("address", 3)],
+        &[("out", WORD)],
+        &["in", "load"],
+    ),
+    (
+        "RAM64",
+        &[("in", WORD), ("load", 1), ("address", 6)],
+        &[("out", WORD)],
+        &["in", "load"],
+    ),
+    (
+        "RAM512",
+        &[("in", WORD), ("load", 1), ("address", 9)],
+        &[("out", WORD)],
+        &["in", "load"],
+    ),
+    (
+        "RAM4K",
+        &[("in", WORD), ("load", 1), ("address", 12)],
+        &[("out", WORD)],
+        &["in", "load"],
+    ),
+    (
+        "RAM16K",
+        &[("in", WORD), ("load", 1), ("address", 14)],
+        &[("out", WORD)],
+        &["in", "load"],
+    ),
+    (
+        "PC",
+        &[("in", WORD), ("load", 1), ("inc", 1), ("reset", 1)],
+        &[("out", WORD)],
+        &["in", "load", "inc", "reset"],
+    ),
+];
+
+/// every builtin chip like `CHIP Nand { IN a, b; OUT out; BUILTIN Nand; }` in n2t_hdl
+pub fn chips() -> Vec<Chip<ComponentMap>> {
+    CHIPS.iter().map(chip).collect()
+}
+
+/// the signatures of every builtin chip for `Chip::check` and `Project::resolve`, the
+/// sequential chips are clocked like in n2t_hdl
+pub fn library() -> Library {
+    let mut library = Library::new();
+    library.add_chips(&chips());
+    library
+}
+
+fn chip((name, inputs, outputs, clocked): &Definition) -> Chip<ComponentMap> {
+    let mut builder = Chip::builder(name);
+    for (input, width) in inputs.iter() {
+        builder = builder.input_signal(pin(input, *width));
+    }
+    for (output, width) in outputs.iter() {
+        builder = builder.output_signal(pin(output, *width));
+    }
+    builder
+        .builtin(name)
+        .clocked(clocked)
+        .build()
+        .expect("the builtin chips are valid")
+}
+
+fn pin(name: &str, width: usize) -> Signal {
+    if width == 1 {
+        Signal::new(name)
+    } else {
+        Signal::bus(name, width)
+    }
+}
+
+// --------------------------------- models ---------------------------------
+
+/// the behaviour of a builtin chip. The value of every pin is a number with the bits of
+/// the pin in the order of the signature, `a[0]` is the lowest bit. Sequential chips
+/// keep their state until `tick`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    name: &'static str,
+    state: Vec<u16>,
+}
+
+impl Model {
+    /// `None` if there is no builtin chip with the name
+    pub fn new(name: &str) -> Option<Self> {
+        let (name, inputs, _, _) = CHIPS.iter().find(|(chip, ..)| *chip == name)?;
+        let size = match inputs.iter().find(|(pin, _)| *pin == "address") {
+            Some((_, width)) => 1 << width,
+            None => 1,
+        };
+        Some(Self {
+            name,
+            state: vec![0; size],
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// a chip with state like `DFF`, `Register` or `RAM8`
+    pub fn is_sequential(&self) -> bool {
+        CHIPS
+            .iter()
+            .any(|(chip, _, _, clocked)| *chip == self.name && !clocked.is_empty())
+    }
+
+    /// the outputs for the inputs, the outputs of a sequential chip only depend on its
+    /// state and on the address of a RAM. Missing inputs are `0`.
+    pub fn eval(&self, inputs: &[u16]) -> Vec<u16> {
+        let get = |i: usize| inputs.get(i).copied().unwrap_or(0);
+        let bit = |i: usize| get(i) & 1 == 1;
+        let from = |b: bool| b as u16;
+
+        match self.name {
+            "Nand" => vec![from(!(bit(0) && bit(1)))],
+            "Not" => vec![from(!bit(0))],
+            "And" => vec![from(bit(0) && bit(1))],
+            "Or" => vec![from(bit(0) || bit(1))],
+            "Xor" => vec![from(bit(0) != bit(1))],
+            "Mux" | "Mux16" => vec![if bit(2) { get(1) } else { get(0) }],
+            "DMux" => select(get(0) & 1, get(1) & 1, 2),
+            "Not16" => vec![!get(0)],
+            "And16" => vec![get(0) & get(1)],
+            "Or16" => vec![get(0) | get(1)],
+            "Or8Way" => vec![from(get(0) & 0xff != 0)],
+            "Mux4Way16" => vec![get((get(4) & 0b11) as usize)],
+            "Mux8Way16" => vec![get((get(8) & 0b111) as usize)],
+            "DMux4Way" => select(get(0) & 1, get(1) & 0b11, 4),
+            "DMux8Way" => select(get(0) & 1, get(1) & 0b111, 8),
+            "HalfAdder" => vec![from(bit(0) != bit(1)), from(bit(0) && bit(1))],
+            "FullAdder" => {
+                let sum = get(0) % 2 + get(1) % 2 + get(2) % 2;
+                vec![sum & 1, sum >> 1]
+            }
+            "Add16" => vec![get(0).wrapping_add(get(1))],
+            "Inc16" => vec![get(0).wrapping_add(1)],
+            "ALU" => alu(
+                get(0),
+                get(1),
+                [bit(2), bit(3), bit(4), bit(5), bit(6), bit(7)],
+            ),
+            name if name.starts_with("RAM") => vec![self.state[get(2) as usize % self.state.len()]],
+            _ => vec![self.state[0]],
+        }
+    }
+
+    /// the clock edge, a sequential chip stores the inputs in its state
+    pub fn tick(&mut self, inputs: &[u16]) {
+        let get = |i: usize| inputs.get(i).copied().unwrap_or(0);
+        let bit = |i: usize| get(i) & 1 == 1;
+
+        match self.name {
+            "DFF" => self.state[0] = get(0) & 1,
+            "Bit" if bit(1) => self.state[0] = get(0) & 1,
+            "Register" if bit(1) => self.state[0] = get(0),
+            "PC" if bit(3) => self.state[0] = 0,
+            "PC" if bit(1) => self.state[0] = get(0),
+            "PC" if bit(2) => self.state[0] = self.state[0].wrapping_add(1),
+            name if name.starts_with("RAM") && bit(1) => {
+                let address = get(2) as usize % self.state.len();
+                self.state[address] = get(0);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `input` on the output `sel` and `0` on the others
+fn select(input: u16, sel: u16, outputs: usize) -> Vec<u16> {
+    (0..outputs)
+        .map(|i| if i == sel as usize { input } else { 0 })
+        .collect()
+}
+
+/// the outputs `out`, `zr` and `ng` for the control bits `zx, nx, zy, ny, f, no`
+fn alu(x: u16, y: u16, [zx, nx, zy, ny, f, no]: [bool; 6]) -> Vec<u16> {
+    let x = if zx { 0 } else { x };
+    let x = if nx { !x } else { x };
+    let y = if zy { 0 } else { y };
+    let y = if ny { !y } else { y };
+    let out = if f { x.wrapping_add(y) } else { x & y };
+    let out = if no { !out } else { out };
+    vec![out, (out == 0) as u16, out >> 15]
+}
@@ -0,0 +1,105 @@
+use crate::convert::{check_arity, check_assignment, io_declarations, lookup, pin_bits};
+use crate::signal::group;
+use crate::{Chip, ComponentIO, ComponentMap, Definition, Error, ErrorKind, Library, Signal};
+
+// The parsers only check the syntax of a chip, these checks need the signatures of the
+// chips that are used as parts, like the library of `Project::resolve`. Every error of
+// the chip is returned and not only the first one.
+
+impl Chip<ComponentMap> {
+    /// every part has to use a known chip, connect only pins of that chip and connect
+    /// every input bit exactly once
+    pub fn check(&self, library: &Library) -> Result<(), Vec<Error>> {
+        let mut errors = Vec::new();
+        for part in &self.parts {
+            let signature = match lookup(library, &part.name, part.span) {
+                Ok(signature) => signature,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+
+            let mut connected: Vec<Signal> = Vec::new();
+            let mut duplicates = Vec::new();
+            for (pin, _) in &part.var_map {
+                match pin_bits(signature, pin, part.span) {
+                    Ok(bits) if signature.has_input(pin.name()) => {
+                        for bit in bits {
+                            if connected.contains(&bit) {
+                                duplicates.push(bit);
+                            } else {
+                                connected.push(bit);
+                            }
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => errors.push(err),
+                }
+            }
+            for bits in group(duplicates) {
+                errors.push(Error::msg_span(
+                    ErrorKind::DuplicatePin,
+                    &format!(
+                        "input <{}> of <{}> is connected more than once",
+                        bits, part.name
+                    ),
+                    part.span,
+                ));
+            }
+
+            for input in signature.inputs() {
+                let bits = input.bits();
+                let missing: Vec<_> = bits
+                    .iter()
+                    .filter(|bit| !connected.contains(bit))
+                    .cloned()
+                    .collect();
+                let missing = match missing.len() {
+                    0 => continue,
+                    len if len == bits.len() => vec![Signal::new(input.name())],
+                    _ => group(missing),
+                };
+                for bits in missing {
+                    errors.push(Error::msg_span(
+                        ErrorKind::UnconnectedInput,
+                        &format!("input <{}> of <{}> is not connected", bits, part.name),
+                        part.span,
+                    ));
+                }
+            }
+        }
+        crate::recovered(self.span.file, (), errors)
+    }
+}
+
+impl Chip<ComponentIO> {
+    /// every part has to use a known chip and have a signal for every input bit, both
+    /// sides of an assignment need the same number of bits. A signal without a range is a
+    /// whole pin of the chip or has the width of the pin it is connected to.
+    pub fn check(&self, library: &Library) -> Result<(), Vec<Error>> {
+        let declarations = io_declarations(self, library);
+        let mut errors = Vec::new();
+        for part in &self.parts {
+            let result = if part.is_assignment() {
+                check_assignment(part, &declarations)
+            } else {
+                lookup(library, &part.name, part.span)
+                    .and_then(|signature| check_arity(part, signature, &declarations))
+            };
+            errors.extend(result.err());
+        }
+        crate::recovered(self.span.file, (), errors)
+    }
+}
+
+impl Definition {
+    /// `Chip::check` of the chip, a lookup table has no parts
+    pub fn check(&self, library: &Library) -> Result<(), Vec<Error>> {
+        match self {
+            Definition::Map(chip) => chip.check(library),
+            Definition::IO(chip) => chip.check(library),
+            Definition::Table(_) => Ok(()),
+        }
+    }
+}
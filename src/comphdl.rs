@@ -1,7 +1,366 @@
 // https://github.com/Badel2/comphdl
 
-use crate::{Chip, ComponentIO, Error};
+use crate::{lexer, Chip, ComponentIO, Error, ErrorKind, FileId, Signal, Span, MAX_NUMBER};
+use logos::Logos;
+use std::iter::Peekable;
+use std::slice::Iter;
 
 pub fn parse(code: &str) -> Result<Vec<Chip<ComponentIO>>, Error> {
-    Ok(Vec::new())
+    crate::first_error(parse_recover(code))
+}
+
+/// like `parse` but continues after an error and returns every error of the file
+pub fn parse_recover(code: &str) -> Result<Vec<Chip<ComponentIO>>, Vec<Error>> {
+    parse_file(FileId::default(), code)
+}
+
+/// like `parse_recover` but the spans of the result point into `file`
+pub fn parse_file(file: FileId, code: &str) -> Result<Vec<Chip<ComponentIO>>, Vec<Error>> {
+    let (tokens, mut errors) = lexer::tokenize(code);
+    let mut t_iter = tokens.iter().peekable();
+
+    let mut chips = Vec::new();
+
+    while t_iter.peek().is_some() {
+        match get_chip(&mut t_iter, file, &mut errors) {
+            Ok(chip) => chips.push(chip),
+            Err(err) => {
+                errors.push(err);
+                sync_chip(&mut t_iter);
+            }
+        }
+    }
+    crate::recovered(file, chips, errors)
+}
+
+fn get_chip(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    errors: &mut Vec<Error>,
+) -> Result<Chip<ComponentIO>, Error> {
+    let first = t_iter.peek().copied();
+    Error::expect(t_iter.next(), TokenType::Component)?;
+    let name = get_identifier(t_iter.next())?;
+
+    Error::expect(t_iter.next(), TokenType::OpenP)?;
+    let inputs = get_names(t_iter, TokenType::CloseP)?;
+    Error::expect(t_iter.next(), TokenType::CloseP)?;
+
+    Error::expect(t_iter.next(), TokenType::Arrow)?;
+    let outputs = get_group(t_iter)?;
+
+    Error::expect(t_iter.next(), TokenType::OpenC)?;
+    let parts = get_parts(t_iter, file, errors);
+    let last = t_iter.next();
+    Error::expect(last, TokenType::CloseC)?;
+
+    Ok(Chip {
+        span: Span::from_tokens(file, first, last),
+        ..Chip::new_signals(&name, inputs, outputs, parts)
+    })
+}
+
+// --------------------------------- components ---------------------------------
+
+/// a broken part is skipped up to its `;` so that the following parts are still checked
+fn get_parts(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    errors: &mut Vec<Error>,
+) -> Vec<ComponentIO> {
+    let mut parts = Vec::new();
+
+    while let Some(&token) = t_iter.peek() {
+        if token.eq_type(TokenType::CloseC) || token.eq_type(TokenType::Component) {
+            break;
+        }
+
+        let is_component = token.eq_type(TokenType::Identifier(String::new()))
+            && matches!(lexer::peek_second(t_iter), Some(token) if token.eq_type(TokenType::OpenP));
+
+        let part = if is_component {
+            get_component(t_iter, file)
+        } else {
+            get_assignment(t_iter, file)
+        };
+        match part {
+            Ok(part) => parts.push(part),
+            Err(err) => {
+                errors.push(err);
+                sync_statement(t_iter);
+            }
+        }
+    }
+
+    parts
+}
+
+fn get_component(t_iter: &mut Peekable<Iter<Token>>, file: FileId) -> Result<ComponentIO, Error> {
+    let first = t_iter.peek().copied();
+    let chip_name = get_identifier(t_iter.next())?;
+
+    Error::expect(t_iter.next(), TokenType::OpenP)?;
+    let inputs = get_names(t_iter, TokenType::CloseP)?;
+    Error::expect(t_iter.next(), TokenType::CloseP)?;
+
+    let mut outputs = Vec::new();
+    if let Some(&token) = t_iter.peek() {
+        if token.eq_type(TokenType::Arrow) {
+            Error::expect(t_iter.next(), TokenType::Arrow)?;
+            outputs = get_group(t_iter)?;
+        }
+    }
+    let last = t_iter.next();
+    Error::expect(last, TokenType::Semicolon)?;
+
+    Ok(ComponentIO {
+        span: Span::from_tokens(file, first, last),
+        ..ComponentIO::new_signals(inputs, outputs, &chip_name)
+    })
+}
+
+fn get_assignment(t_iter: &mut Peekable<Iter<Token>>, file: FileId) -> Result<ComponentIO, Error> {
+    let first = t_iter.peek().copied();
+    let inputs = get_group(t_iter)?;
+    Error::expect(t_iter.next(), TokenType::Equals)?;
+    let outputs = get_group(t_iter)?;
+    let last = t_iter.next();
+    Error::expect(last, TokenType::Semicolon)?;
+
+    Ok(ComponentIO {
+        span: Span::from_tokens(file, first, last),
+        ..ComponentIO::new_signals(inputs, outputs, "")
+    })
+}
+
+// --------------------------------- printing ---------------------------------
+
+/// formatted source of the components, parsing it again gives the same components
+pub fn to_string(chips: &[Chip<ComponentIO>]) -> String {
+    chips
+        .iter()
+        .map(chip_to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn chip_to_string(chip: &Chip<ComponentIO>) -> String {
+    let mut code = format!(
+        "component {}({}) -> {} {{\n",
+        chip.name(),
+        names_to_string(chip.inputs()),
+        group_to_string(chip.outputs())
+    );
+
+    for part in chip.parts() {
+        if part.is_assignment() {
+            code.push_str(&format!(
+                "    {} = {};\n",
+                group_to_string(part.inputs()),
+                group_to_string(part.outputs())
+            ));
+        } else if part.outputs().is_empty() {
+            code.push_str(&format!(
+                "    {}({});\n",
+                part.name(),
+                names_to_string(part.inputs())
+            ));
+        } else {
+            code.push_str(&format!(
+                "    {}({}) -> {};\n",
+                part.name(),
+                names_to_string(part.inputs()),
+                group_to_string(part.outputs())
+            ));
+        }
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+/// a single name is written without parentheses
+fn group_to_string(names: &[Signal]) -> String {
+    match names {
+        [name] => name_to_string(name),
+        _ => format!("({})", names_to_string(names)),
+    }
+}
+
+fn names_to_string(names: &[Signal]) -> String {
+    names
+        .iter()
+        .map(name_to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `a`, `a[3]` or `a[7:0]`
+fn name_to_string(name: &Signal) -> String {
+    match name.range() {
+        Some((first, last)) if first != last => format!("{}[{}:{}]", name.name(), first, last),
+        _ => name.to_string(),
+    }
+}
+
+// --------------------------------- recovery ---------------------------------
+
+/// skips the rest of a statement including the `;`, a `}` or the next component is kept
+fn sync_statement(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(
+        t_iter,
+        &[TokenType::CloseC, TokenType::Component],
+        &[TokenType::Semicolon],
+    );
+}
+
+/// skips the rest of a component including the `}`
+fn sync_chip(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(t_iter, &[TokenType::Component], &[TokenType::CloseC]);
+}
+
+// --------------------------------- utils ---------------------------------
+
+/// either a single name like `x[1:0]` or a tuple like `(a, b, c)`
+fn get_group(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<Signal>, Error> {
+    if let Some(&token) = t_iter.peek() {
+        if token.eq_type(TokenType::OpenP) {
+            Error::expect(t_iter.next(), TokenType::OpenP)?;
+            let names = get_names(t_iter, TokenType::CloseP)?;
+            Error::expect(t_iter.next(), TokenType::CloseP)?;
+            return Ok(names);
+        }
+    }
+    Ok(vec![get_name(t_iter)?])
+}
+
+/// comma separated names, the list may be empty if the next token is `end`
+fn get_names(t_iter: &mut Peekable<Iter<Token>>, end: TokenType) -> Result<Vec<Signal>, Error> {
+    if let Some(&token) = t_iter.peek() {
+        if token.eq_type(end) {
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut names = vec![get_name(t_iter)?];
+    while let Some(&token) = t_iter.peek() {
+        if !token.eq_type(TokenType::Comma) {
+            break;
+        }
+        Error::expect(t_iter.next(), TokenType::Comma)?;
+        names.push(get_name(t_iter)?);
+    }
+
+    Ok(names)
+}
+
+/// comphdl allows both `a[3:0]` and `a[0:3]`, the order is kept as written
+fn get_name(t_iter: &mut Peekable<Iter<Token>>) -> Result<Signal, Error> {
+    let identifier = get_identifier(t_iter.next())?;
+    if let Some(&token) = t_iter.peek() {
+        if !token.eq_type(TokenType::OpenB) {
+            return Ok(Signal::new(&identifier));
+        }
+        Error::expect(t_iter.next(), TokenType::OpenB)?;
+        let start = get_num(t_iter.next())?;
+        let mut end = start;
+        if let Some(&token) = t_iter.peek() {
+            if token.eq_type(TokenType::Colon) {
+                Error::expect(t_iter.next(), TokenType::Colon)?;
+                end = get_num(t_iter.next())?;
+            }
+        }
+        Error::expect(t_iter.next(), TokenType::CloseB)?;
+        return Ok(Signal::slice(&identifier, start, end));
+    }
+    Ok(Signal::new(&identifier))
+}
+
+fn get_num(token: Option<&Token>) -> Result<usize, Error> {
+    match Error::expect(token, TokenType::Number(0))? {
+        TokenType::Number(num) if num <= MAX_NUMBER => Ok(num),
+        _ => {
+            Err(Error::msg_option("number is too large", token)
+                .with_kind(ErrorKind::NumberTooLarge))
+        }
+    }
+}
+
+fn get_identifier(token: Option<&Token>) -> Result<String, Error> {
+    match Error::expect(token, TokenType::Identifier(String::new()))? {
+        TokenType::Identifier(name) => Ok(name),
+        _ => Err(Error::msg_option("expected identifier", token)),
+    }
+}
+
+// ------------------------------- tokens ------------------------------------------------
+
+type Token = lexer::Token<TokenType>;
+
+#[derive(Logos, Debug, Clone)]
+enum TokenType {
+    #[token("component")]
+    Component,
+
+    #[token("{")]
+    OpenC,
+    #[token("}")]
+    CloseC,
+    #[token("(")]
+    OpenP,
+    #[token(")")]
+    CloseP,
+    #[token("[")]
+    OpenB,
+    #[token("]")]
+    CloseB,
+
+    #[token(",")]
+    Comma,
+    #[token(";")]
+    Semicolon,
+    #[token("=")]
+    Equals,
+    #[token("->")]
+    Arrow,
+    #[token(":")]
+    Colon,
+
+    #[token("\t", lexer::ignore)]
+    #[token(" ", lexer::ignore)]
+    #[token("\r", lexer::ignore)]
+    #[token("\n", lexer::ignore)]
+    #[regex(r"//[^\n]*", lexer::ignore)]
+    #[token("/*", lexer::block_comment)]
+    Ignore(usize),
+
+    #[regex(r"[a-zA-Z_$][a-zA-Z_$0-9]+", |lex| lex.slice().parse())]
+    #[regex(r"[a-zA-Z]", |lex| lex.slice().parse())]
+    Identifier(String),
+
+    #[regex(r"[0-9]+", lexer::number)]
+    Number(usize),
+
+    #[error]
+    Unknown,
+}
+
+/// the value of a number, identifier or table is not compared
+impl PartialEq for TokenType {
+    fn eq(&self, other: &TokenType) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl lexer::Skip for TokenType {
+    fn newlines(&self) -> Option<usize> {
+        match self {
+            TokenType::Ignore(newlines) => Some(*newlines),
+            _ => None,
+        }
+    }
+
+    fn is_unknown(&self) -> bool {
+        matches!(self, TokenType::Unknown)
+    }
 }
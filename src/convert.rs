@@ -0,0 +1,577 @@
+use crate::signal::{flatten, group, join, resolve, resolve_all};
+use crate::{
+    Chip, Component, ComponentIO, ComponentMap, Error, ErrorKind, Library, Signal, Signature, Span,
+};
+use std::collections::{HashMap, HashSet};
+
+// `Component` has named inputs and outputs and is used between the named pins of n2t_hdl
+// and the positional signals of shdl and comphdl, both need the signature of the chip of
+// every part. Positional signals are matched to the pins bit by bit.
+
+impl Component {
+    /// splits the pins of a n2t_hdl part into inputs and outputs
+    pub fn from_map(part: &ComponentMap, library: &Library) -> Result<Self, Error> {
+        let signature = lookup(library, &part.name, part.span)?;
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        for (pin, signal) in &part.var_map {
+            pin_bits(signature, pin, part.span)?;
+            if signature.has_input(pin.name()) {
+                inputs.push((pin.clone(), signal.clone()));
+            } else {
+                outputs.push((pin.clone(), signal.clone()));
+            }
+        }
+
+        Ok(Self {
+            inputs,
+            ouputs: outputs,
+            name: part.name.clone(),
+            span: part.span,
+        })
+    }
+
+    /// names the signals of a shdl or comphdl part with the pins of its chip, every input
+    /// has to be connected but trailing outputs can be left out. An assignment has no chip
+    /// and returns an `Unsupported` error. Without the chip of the part a signal without a
+    /// range that starts at a pin has the width of the pin, see `positional_bits`.
+    pub fn from_io(part: &ComponentIO, library: &Library) -> Result<Self, Error> {
+        let bits = from_io_bits(part, library, &[])?;
+        Ok(Self {
+            inputs: join(bits.inputs),
+            ouputs: join(bits.ouputs),
+            ..bits
+        })
+    }
+}
+
+impl From<&Component> for ComponentMap {
+    fn from(part: &Component) -> Self {
+        Self {
+            var_map: part.inputs.iter().chain(&part.ouputs).cloned().collect(),
+            name: part.name.clone(),
+            span: part.span,
+        }
+    }
+}
+
+impl Chip<ComponentMap> {
+    pub fn to_component(&self, library: &Library) -> Result<Chip<Component>, Error> {
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| Component::from_map(part, library))
+            .collect::<Result<_, _>>()?;
+        Ok(with_parts(self, parts))
+    }
+
+    /// n2t_hdl to shdl or comphdl, see `Chip<Component>::to_component_io`
+    pub fn to_component_io(&self, library: &Library) -> Result<Chip<ComponentIO>, Error> {
+        self.to_component(library)?.to_component_io(library)
+    }
+}
+
+impl Chip<Component> {
+    pub fn to_component_map(&self) -> Chip<ComponentMap> {
+        with_parts(self, self.parts.iter().map(ComponentMap::from).collect())
+    }
+
+    /// an input that is not connected is `false` like in n2t_hdl and an output that is
+    /// not connected gets a new signal. An output that is connected to more than one
+    /// signal drives the others with assignments.
+    pub fn to_component_io(&self, library: &Library) -> Result<Chip<ComponentIO>, Error> {
+        let declarations: Vec<Signal> = self.pins().cloned().collect();
+        let mut names: HashSet<String> = declarations
+            .iter()
+            .map(|pin| pin.name().to_string())
+            .collect();
+        for part in &self.parts {
+            let signals = part.inputs.iter().chain(&part.ouputs);
+            names.extend(signals.map(|(_, signal)| signal.name().to_string()));
+        }
+
+        let mut parts = Vec::new();
+        for part in &self.parts {
+            let signature = lookup(library, &part.name, part.span)?;
+            for (pin, _) in &part.inputs {
+                if !signature.has_input(pin.name()) {
+                    return Err(unknown_pin(&part.name, pin, part.span));
+                }
+            }
+            for (pin, _) in &part.ouputs {
+                if !signature.has_output(pin.name()) {
+                    return Err(unknown_pin(&part.name, pin, part.span));
+                }
+            }
+            let input_bits = connection_bits(&part.inputs, signature, &declarations, part.span)?;
+            let output_bits = connection_bits(&part.ouputs, signature, &declarations, part.span)?;
+
+            let mut inputs = Vec::new();
+            for bit in flatten(signature.inputs()) {
+                let signals = connected(&input_bits, &bit);
+                match signals.as_slice() {
+                    [] => inputs.push(Signal::new("false")),
+                    [signal] => inputs.push((*signal).clone()),
+                    _ => {
+                        return Err(Error::msg_span(
+                            ErrorKind::DuplicatePin,
+                            &format!(
+                                "input <{}> of <{}> is connected more than once",
+                                bit, part.name
+                            ),
+                            part.span,
+                        ))
+                    }
+                }
+            }
+
+            let mut outputs = Vec::new();
+            let mut assignments = Vec::new();
+            let mut used = 0;
+            for pin in signature.outputs() {
+                let mut unused = None;
+                for bit in pin.bits() {
+                    let signals = connected(&output_bits, &bit);
+                    let signal = match signals.first() {
+                        Some(&signal) => {
+                            used = outputs.len() + 1;
+                            signal.clone()
+                        }
+                        None => {
+                            let name = unused
+                                .get_or_insert_with(|| new_name(&mut names, pin.name()))
+                                .clone();
+                            match bit.range() {
+                                Some((i, _)) => Signal::index(&name, i),
+                                None => Signal::new_string(name),
+                            }
+                        }
+                    };
+                    for &other in signals.iter().skip(1) {
+                        assignments.push((signal.clone(), other.clone()));
+                    }
+                    outputs.push(signal);
+                }
+            }
+            // trailing outputs that are not connected can be left out
+            outputs.truncate(used);
+
+            parts.push(ComponentIO {
+                inputs: group(inputs),
+                ouputs: group(outputs),
+                name: part.name.clone(),
+                span: part.span,
+            });
+            parts.extend(
+                join(assignments)
+                    .into_iter()
+                    .map(|(source, target)| ComponentIO {
+                        inputs: vec![source],
+                        ouputs: vec![target],
+                        name: String::new(),
+                        span: part.span,
+                    }),
+            );
+        }
+
+        Ok(with_parts(self, parts))
+    }
+}
+
+impl Chip<ComponentIO> {
+    /// fails for chips with assignments, see `to_component_map`
+    pub fn to_component(&self, library: &Library) -> Result<Chip<Component>, Error> {
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| Component::from_io(part, library))
+            .collect::<Result<_, _>>()?;
+        Ok(with_parts(self, parts))
+    }
+
+    /// shdl or comphdl to n2t_hdl. n2t_hdl has no assignments so the output of the part
+    /// that drives the source of an assignment is also connected to its target, and an
+    /// internal target is replaced by its source. Assignments from an input or a constant
+    /// to an output of the chip need a part and return an `Unsupported` error. Buses like
+    /// `a[3:0]` are declared in ascending order.
+    pub fn to_component_map(&self, library: &Library) -> Result<Chip<ComponentMap>, Error> {
+        let declared = io_declarations(self, library);
+        let mut parts = Vec::new();
+        let mut assignments = Vec::new();
+        for part in &self.parts {
+            if part.is_assignment() {
+                assignments.push(part);
+            } else {
+                parts.push(from_io_bits(part, library, &declared)?);
+            }
+        }
+
+        let mut replaced: HashMap<Signal, Signal> = HashMap::new();
+        for assignment in assignments {
+            check_assignment(assignment, &declared)?;
+            let sources = resolve_all(&assignment.inputs, &declared);
+            let targets = resolve_all(&assignment.ouputs, &declared);
+
+            for (source, target) in sources.into_iter().zip(targets) {
+                let source = replaced.get(&source).cloned().unwrap_or(source);
+                let driver = parts.iter_mut().find_map(|part: &mut Component| {
+                    let pin = part
+                        .ouputs
+                        .iter()
+                        .find(|(_, signal)| signal == &source)?
+                        .0
+                        .clone();
+                    Some((part, pin))
+                });
+
+                if let Some((part, pin)) = driver {
+                    part.ouputs.push((pin, target));
+                } else if !self.pins().any(|pin| pin.name() == target.name()) {
+                    for part in &mut parts {
+                        for (_, signal) in part.inputs.iter_mut().chain(&mut part.ouputs) {
+                            if signal == &target {
+                                *signal = source.clone();
+                            }
+                        }
+                    }
+                    replaced.insert(target, source);
+                } else {
+                    return Err(Error::msg_span(
+                        ErrorKind::Unsupported,
+                        &format!("<{} = {}> needs a part in n2t_hdl", target, source),
+                        assignment.span,
+                    ));
+                }
+            }
+        }
+
+        let inputs: Vec<Signal> = self.inputs.iter().map(ascending).collect();
+        let outputs: Vec<Signal> = self.outputs.iter().map(ascending).collect();
+        // the internal buses follow the pins of the chip in `declared`
+        let internal = &declared[self.inputs.len() + self.outputs.len()..];
+        let declarations: Vec<Signal> = inputs
+            .iter()
+            .chain(&outputs)
+            .chain(internal)
+            .cloned()
+            .collect();
+        let mut joined = Vec::new();
+        for part in parts {
+            let signature = lookup(library, &part.name, part.span)?;
+            let connections =
+                |bits: Vec<(Signal, Signal)>, pins: &[Signal]| -> Vec<(Signal, Signal)> {
+                    join(bits)
+                        .into_iter()
+                        .map(|(pin, signal)| (whole(pin, pins), whole(signal, &declarations)))
+                        .collect()
+                };
+            joined.push(Component {
+                inputs: connections(part.inputs, signature.inputs()),
+                ouputs: connections(part.ouputs, signature.outputs()),
+                ..part
+            });
+        }
+        let chip = Chip {
+            inputs,
+            outputs,
+            ..with_parts(self, joined)
+        };
+        Ok(chip.to_component_map())
+    }
+}
+
+// --------------------------------- utils ---------------------------------
+
+pub(crate) fn lookup<'a>(
+    library: &'a Library,
+    name: &str,
+    span: Span,
+) -> Result<&'a Signature, Error> {
+    library.get(name).ok_or_else(|| {
+        Error::msg_span(
+            ErrorKind::UnknownChip,
+            &format!("unknown chip <{}>", name),
+            span,
+        )
+    })
+}
+
+pub(crate) fn unknown_pin(chip: &str, pin: &Signal, span: Span) -> Error {
+    Error::msg_span(
+        ErrorKind::UnknownSignal,
+        &format!("chip <{}> has no pin <{}>", chip, pin),
+        span,
+    )
+}
+
+/// `from_io` with a connection for every bit
+fn from_io_bits(
+    part: &ComponentIO,
+    library: &Library,
+    declarations: &[Signal],
+) -> Result<Component, Error> {
+    if part.is_assignment() {
+        return Err(Error::msg_span(
+            ErrorKind::Unsupported,
+            &format!(
+                "the assignment <{} = {}> is not a part",
+                signals_to_string(&part.ouputs),
+                signals_to_string(&part.inputs)
+            ),
+            part.span,
+        ));
+    }
+    let signature = lookup(library, &part.name, part.span)?;
+    check_arity(part, signature, declarations)?;
+    let pins = (flatten(signature.inputs()), flatten(signature.outputs()));
+    let signals = (
+        positional_bits(&part.inputs, signature.inputs(), declarations),
+        positional_bits(&part.ouputs, signature.outputs(), declarations),
+    );
+
+    Ok(Component {
+        inputs: pins.0.into_iter().zip(signals.0).collect(),
+        ouputs: pins.1.into_iter().zip(signals.1).collect(),
+        name: part.name.clone(),
+        span: part.span,
+    })
+}
+
+/// every input bit of the chip needs a signal but trailing outputs can be left out
+pub(crate) fn check_arity(
+    part: &ComponentIO,
+    signature: &Signature,
+    declarations: &[Signal],
+) -> Result<(), Error> {
+    let pins = (flatten(signature.inputs()), flatten(signature.outputs()));
+    let signals = (
+        positional_bits(&part.inputs, signature.inputs(), declarations),
+        positional_bits(&part.ouputs, signature.outputs(), declarations),
+    );
+    if signals.0.len() == pins.0.len() && signals.1.len() <= pins.1.len() {
+        return Ok(());
+    }
+    let msg = format!(
+        "chip <{}> has {} input and {} output bits but the part has {} input and {} output bits",
+        part.name,
+        pins.0.len(),
+        pins.1.len(),
+        signals.0.len(),
+        signals.1.len()
+    );
+    Err(Error::msg_span(ErrorKind::ArityMismatch, &msg, part.span))
+}
+
+/// both sides of an assignment need the same number of bits
+pub(crate) fn check_assignment(
+    assignment: &ComponentIO,
+    declarations: &[Signal],
+) -> Result<(), Error> {
+    let sources = resolve_all(&assignment.inputs, declarations).len();
+    let targets = resolve_all(&assignment.ouputs, declarations).len();
+    if sources == targets {
+        return Ok(());
+    }
+    let msg = format!("assignment of {} bits to {} bits", sources, targets);
+    Err(Error::msg_span(
+        ErrorKind::ArityMismatch,
+        &msg,
+        assignment.span,
+    ))
+}
+
+/// the bits of a pin, a pin without a range is the whole declared pin
+pub(crate) fn pin_bits(
+    signature: &Signature,
+    pin: &Signal,
+    span: Span,
+) -> Result<Vec<Signal>, Error> {
+    let declaration = signature
+        .pin(pin.name())
+        .ok_or_else(|| unknown_pin(signature.name(), pin, span))?;
+    if !pin.is_bus() {
+        return Ok(declaration.bits());
+    }
+    let declared = declaration.bits();
+    let bits = pin.bits();
+    if !bits.iter().all(|bit| declared.contains(bit)) {
+        return Err(unknown_pin(signature.name(), pin, span));
+    }
+    Ok(bits)
+}
+
+/// every `pin=signal` as connections of single bits. A signal without a range is a
+/// constant, a whole pin of the chip or an internal bus with the width of the pin.
+pub(crate) fn connection_bits(
+    pins: &[(Signal, Signal)],
+    signature: &Signature,
+    declarations: &[Signal],
+    span: Span,
+) -> Result<Vec<(Signal, Signal)>, Error> {
+    let mut bits = Vec::new();
+    for (pin, signal) in pins {
+        let pin_bits = pin_bits(signature, pin, span)?;
+        let width = pin_bits.len();
+        let declaration = declarations.iter().find(|d| d.name() == signal.name());
+        let signal_bits = match declaration {
+            _ if signal.is_bus() => signal.bits(),
+            Some(declaration) if signal != "true" && signal != "false" => declaration.bits(),
+            _ => undeclared_bits(signal, width),
+        };
+        if signal_bits.len() != width {
+            let msg = format!(
+                "width mismatch <{}> has {} bits but <{}> has {} bits",
+                pin,
+                width,
+                signal,
+                signal_bits.len()
+            );
+            return Err(Error::msg_span(ErrorKind::WidthMismatch, &msg, span));
+        }
+        bits.extend(pin_bits.into_iter().zip(signal_bits));
+    }
+    Ok(bits)
+}
+
+/// the positional signals of a part as single bits. A signal without a range is resolved
+/// with `declarations`, an undeclared one that starts at a pin of the chip like the
+/// internal `x` in `Not4(x)` gets the width of that pin.
+pub(crate) fn positional_bits(
+    signals: &[Signal],
+    pins: &[Signal],
+    declarations: &[Signal],
+) -> Vec<Signal> {
+    positional(signals, pins, declarations)
+        .into_iter()
+        .flat_map(|(_, bits)| bits)
+        .collect()
+}
+
+/// the pins of a shdl or comphdl chip followed by every internal bus that is connected to
+/// a whole pin of a part that is wider than one bit, like `x` in `Not4(a) -> x`
+pub(crate) fn io_declarations(chip: &Chip<ComponentIO>, library: &Library) -> Vec<Signal> {
+    let mut declarations: Vec<Signal> = chip.pins().cloned().collect();
+    for part in &chip.parts {
+        let signature = match library.get(&part.name) {
+            Some(signature) if !part.is_assignment() => signature,
+            _ => continue,
+        };
+        let inputs = positional(&part.inputs, signature.inputs(), &declarations);
+        let outputs = positional(&part.ouputs, signature.outputs(), &declarations);
+        for (signal, bits) in inputs.into_iter().chain(outputs) {
+            let known = signal.is_bus()
+                || signal == "true"
+                || signal == "false"
+                || declarations.iter().any(|d| d.name() == signal.name());
+            if !known && bits.len() > 1 {
+                declarations.push(Signal::bus(signal.name(), bits.len()));
+            }
+        }
+    }
+    declarations
+}
+
+/// every signal with its bits, see `positional_bits`
+pub(crate) fn positional<'a>(
+    signals: &'a [Signal],
+    pins: &[Signal],
+    declarations: &[Signal],
+) -> Vec<(&'a Signal, Vec<Signal>)> {
+    let mut offset = 0;
+    signals
+        .iter()
+        .map(|signal| {
+            let declared = declarations.iter().any(|d| d.name() == signal.name());
+            let bits = match pin_at(pins, offset) {
+                Some(pin) if !signal.is_bus() && !declared => undeclared_bits(signal, pin.width()),
+                _ => resolve(signal, declarations),
+            };
+            offset += bits.len();
+            (signal, bits)
+        })
+        .collect()
+}
+
+/// the pin whose first bit is the bit `offset` of all pins
+fn pin_at(pins: &[Signal], offset: usize) -> Option<&Signal> {
+    let mut start = 0;
+    for pin in pins {
+        if start == offset {
+            return Some(pin);
+        }
+        start += pin.width();
+    }
+    None
+}
+
+/// a constant or an internal signal without a range that is connected to a whole pin
+fn undeclared_bits(signal: &Signal, width: usize) -> Vec<Signal> {
+    if signal == "true" || signal == "false" {
+        vec![signal.clone(); width]
+    } else if width == 1 {
+        vec![signal.clone()]
+    } else {
+        (0..width)
+            .map(|i| Signal::index(signal.name(), i))
+            .collect()
+    }
+}
+
+/// every signal that is connected to the bit `pin`
+fn connected<'a>(bits: &'a [(Signal, Signal)], pin: &Signal) -> Vec<&'a Signal> {
+    bits.iter()
+        .filter(|(name, _)| name == pin)
+        .map(|(_, signal)| signal)
+        .collect()
+}
+
+/// `unused_<pin>` with a number if the name is already taken
+fn new_name(names: &mut HashSet<String>, pin: &str) -> String {
+    let mut name = format!("unused_{}", pin);
+    let mut i = 1;
+    while names.contains(&name) {
+        name = format!("unused_{}{}", pin, i);
+        i += 1;
+    }
+    names.insert(name.clone());
+    name
+}
+
+/// `a[3:0]` as `a[0..3]`, n2t_hdl has no descending buses
+fn ascending(signal: &Signal) -> Signal {
+    match signal.range() {
+        Some((first, last)) if first > last => Signal::slice(signal.name(), last, first),
+        _ => signal.clone(),
+    }
+}
+
+/// a slice that covers a whole declared bus like `a[0..15]` as `a`
+fn whole(signal: Signal, declarations: &[Signal]) -> Signal {
+    let declaration = declarations.iter().find(|d| d.name() == signal.name());
+    match declaration {
+        Some(declaration) if declaration.is_bus() && declaration.range() == signal.range() => {
+            Signal::new(signal.name())
+        }
+        _ => signal,
+    }
+}
+
+fn signals_to_string(signals: &[Signal]) -> String {
+    signals
+        .iter()
+        .map(Signal::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn with_parts<T, U>(chip: &Chip<T>, parts: Vec<U>) -> Chip<U> {
+    Chip {
+        name: chip.name.clone(),
+        inputs: chip.inputs.clone(),
+        outputs: chip.outputs.clone(),
+        parts,
+        builtin: chip.builtin.clone(),
+        clocked: chip.clocked.clone(),
+        span: chip.span,
+    }
+}
@@ -0,0 +1,225 @@
+use crate::open_gal::OGalParse;
+use crate::shdl::ShdlParse;
+use crate::{
+    comphdl, lexer, n2t_hdl, open_gal, shdl, Chip, ComponentIO, ComponentMap, Error, ErrorKind,
+    FileId,
+};
+use logos::Logos;
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    N2tHdl,
+    Shdl,
+    Comphdl,
+    OpenGal,
+}
+
+impl Dialect {
+    /// the name of the module of the dialect
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::N2tHdl => "n2t_hdl",
+            Dialect::Shdl => "shdl",
+            Dialect::Comphdl => "comphdl",
+            Dialect::OpenGal => "open_gal",
+        }
+    }
+
+    /// parses `code` with the parser of the dialect
+    pub fn parse(&self, code: &str) -> Result<HdlParse, Error> {
+        Ok(match self {
+            Dialect::N2tHdl => HdlParse::N2tHdl(n2t_hdl::parse(code)?),
+            Dialect::Shdl => HdlParse::Shdl(shdl::parse(code)?),
+            Dialect::Comphdl => HdlParse::Comphdl(comphdl::parse(code)?),
+            Dialect::OpenGal => HdlParse::OpenGal(open_gal::parse(code)?),
+        })
+    }
+
+    /// like `parse` but with every error and the spans of the result point into `file`
+    pub fn parse_file(&self, file: FileId, code: &str) -> Result<HdlParse, Vec<Error>> {
+        Ok(match self {
+            Dialect::N2tHdl => HdlParse::N2tHdl(n2t_hdl::parse_file(file, code)?),
+            Dialect::Shdl => HdlParse::Shdl(shdl::parse_file(file, code)?),
+            Dialect::Comphdl => HdlParse::Comphdl(comphdl::parse_file(file, code)?),
+            Dialect::OpenGal => HdlParse::OpenGal(open_gal::parse_file(file, code)?),
+        })
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "CHIP" => Some(Dialect::N2tHdl),
+            "chip" => Some(Dialect::Shdl),
+            "component" => Some(Dialect::Comphdl),
+            "pin" | "table" => Some(Dialect::OpenGal),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// the result of any of the parsers
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HdlParse {
+    N2tHdl(Vec<Chip<ComponentMap>>),
+    Shdl(ShdlParse),
+    Comphdl(Vec<Chip<ComponentIO>>),
+    OpenGal(OGalParse),
+}
+
+impl HdlParse {
+    pub fn dialect(&self) -> Dialect {
+        match self {
+            HdlParse::N2tHdl(_) => Dialect::N2tHdl,
+            HdlParse::Shdl(_) => Dialect::Shdl,
+            HdlParse::Comphdl(_) => Dialect::Comphdl,
+            HdlParse::OpenGal(_) => Dialect::OpenGal,
+        }
+    }
+}
+
+/// detects the dialect with `detect` and parses `code` with it
+pub fn parse_any(code: &str) -> Result<HdlParse, Error> {
+    detect(code)?.parse(code)
+}
+
+/// the dialect is chosen by the keywords that start the top level statements:
+/// `CHIP` is n2t_hdl, `chip` is shdl, `component` is comphdl and `pin` or `table`
+/// is open_gal. A file with only functions like `a = b & c;` is open_gal too.
+pub fn detect(code: &str) -> Result<Dialect, Error> {
+    // the unknown text is only an unterminated block comment, which the parser reports
+    let (tokens, _) = lexer::tokenize::<TokenType>(code);
+
+    let mut depth = 0usize;
+    let mut leading = true;
+    let mut first = None;
+    let mut found: Vec<(Dialect, &Token)> = Vec::new();
+    let mut is_func = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match &token.token_type {
+            TokenType::Word(word) if leading && depth == 0 => {
+                first = first.or(Some(token));
+                // `a = ...` and `a.dff` are only allowed at the top level of open_gal
+                let next = tokens.get(i + 1).map(|next| &next.token_type);
+                if matches!(next, Some(TokenType::Equals | TokenType::Dot)) {
+                    is_func = true;
+                } else if let Some(dialect) = Dialect::from_keyword(word) {
+                    if found.iter().all(|(d, _)| *d != dialect) {
+                        found.push((dialect, token));
+                    }
+                }
+                leading = false;
+            }
+            TokenType::OpenC => {
+                depth += 1;
+                leading = false;
+            }
+            TokenType::CloseC => {
+                depth = depth.saturating_sub(1);
+                leading = depth == 0;
+            }
+            TokenType::Semicolon => leading = depth == 0,
+            _ => leading = false,
+        }
+    }
+
+    match found.as_slice() {
+        [(dialect, _)] => Ok(*dialect),
+        [(first_dialect, first_token), (dialect, token), ..] => Err(Error::msg_token(
+            &format!(
+                "ambiguous dialect <{}> on line {} is {} but <{}> is {}",
+                first_token.text(),
+                first_token.line + 1,
+                first_dialect,
+                token.text(),
+                dialect
+            ),
+            (*token).clone(),
+        )
+        .with_kind(ErrorKind::AmbiguousDialect)),
+        [] if is_func => Ok(Dialect::OpenGal),
+        [] => Err(match first.or_else(|| tokens.first()) {
+            Some(token) => Error::msg_token(
+                &format!(
+                    "unknown dialect expected <CHIP>, <chip>, <component>, <pin> or <table> but got <{}>",
+                    token.text()
+                ),
+                token.clone(),
+            ),
+            None => Error::msg(ErrorKind::UnknownDialect, "unknown dialect the file is empty"),
+        }
+        .with_kind(ErrorKind::UnknownDialect)),
+    }
+}
+
+// ------------------------------- tokens ------------------------------------------------
+
+type Token = lexer::Token<TokenType>;
+
+impl Token {
+    fn text(&self) -> &str {
+        match &self.token_type {
+            TokenType::Word(text) | TokenType::Other(text) => text,
+            TokenType::OpenC => "{",
+            TokenType::CloseC => "}",
+            TokenType::Semicolon => ";",
+            TokenType::Equals => "=",
+            TokenType::Dot => ".",
+            _ => "",
+        }
+    }
+}
+
+/// only the tokens that are needed to find the top level statements of every dialect
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum TokenType {
+    #[token("{")]
+    OpenC,
+    #[token("}")]
+    CloseC,
+    #[token(";")]
+    Semicolon,
+    #[token("=")]
+    Equals,
+    #[token(".")]
+    Dot,
+
+    #[token("\t", lexer::ignore)]
+    #[token(" ", lexer::ignore)]
+    #[token("\r", lexer::ignore)]
+    #[token("\n", lexer::ignore)]
+    #[regex(r"//[^\n]*", lexer::ignore)]
+    #[token("/*", lexer::block_comment)]
+    Ignore(usize),
+
+    #[regex(r"[a-zA-Z_$][a-zA-Z_$0-9]*", |lex| lex.slice().to_string())]
+    #[regex(r"[0-9]+", |lex| lex.slice().to_string())]
+    Word(String),
+
+    /// any other character, these are only reported by the parser of the dialect
+    #[regex(r"[^ \t\r\n]", |lex| lex.slice().to_string(), priority = 0)]
+    Other(String),
+
+    #[error]
+    Unknown,
+}
+
+impl lexer::Skip for TokenType {
+    fn newlines(&self) -> Option<usize> {
+        match self {
+            TokenType::Ignore(newlines) => Some(*newlines),
+            _ => None,
+        }
+    }
+
+    fn is_unknown(&self) -> bool {
+        matches!(self, TokenType::Unknown)
+    }
+}
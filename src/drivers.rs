@@ -0,0 +1,198 @@
+use crate::convert::{connection_bits, io_declarations, lookup, positional_bits};
+use crate::signal::{flatten, group, resolve_all};
+use crate::{
+    Chip, Component, ComponentIO, ComponentMap, Definition, Error, ErrorKind, Library, Signal, Span,
+};
+use std::collections::HashMap;
+
+// Every bit of a chip needs exactly one driver: an input of the chip, a constant or an
+// output of a part. The analysis works on single bits so that `a[0..3]` and `a[2]` are
+// the same net, the findings are merged into slices again for the messages.
+
+impl Chip<ComponentMap> {
+    /// the errors and warnings of the nets of the chip, see `Error::is_warning`. The
+    /// library is needed to know which pins of a part are outputs, a part with an unknown
+    /// chip or pin only returns the errors of `check`.
+    pub fn check_drivers(&self, library: &Library) -> Vec<Error> {
+        let declarations: Vec<Signal> = self.pins().cloned().collect();
+        let mut parts = Vec::new();
+        let mut errors = Vec::new();
+        for part in &self.parts {
+            let bits = Component::from_map(part, library).and_then(|component| {
+                let signature = lookup(library, &part.name, part.span)?;
+                let reads =
+                    connection_bits(&component.inputs, signature, &declarations, part.span)?;
+                let writes =
+                    connection_bits(&component.ouputs, signature, &declarations, part.span)?;
+                Ok(PartBits {
+                    reads: reads.into_iter().map(|(_, signal)| signal).collect(),
+                    writes: writes.into_iter().map(|(_, signal)| signal).collect(),
+                    span: part.span,
+                })
+            });
+            match bits {
+                Ok(bits) => parts.push(bits),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if !errors.is_empty() {
+            return errors;
+        }
+        analyze(self, parts)
+    }
+}
+
+impl Chip<ComponentIO> {
+    /// the errors and warnings of the nets of the chip, see `Error::is_warning`. A signal
+    /// without a range is a whole pin of the chip, the library gives the width of an
+    /// internal bus like `n` in `Not4(a) -> n`. The signals of a part with an unknown chip
+    /// are read as they are written.
+    pub fn check_drivers(&self, library: &Library) -> Vec<Error> {
+        let declarations = io_declarations(self, library);
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| match library.get(&part.name) {
+                Some(signature) if !part.is_assignment() => PartBits {
+                    reads: positional_bits(&part.inputs, signature.inputs(), &declarations),
+                    writes: positional_bits(&part.ouputs, signature.outputs(), &declarations),
+                    span: part.span,
+                },
+                _ => PartBits {
+                    reads: resolve_all(&part.inputs, &declarations),
+                    writes: resolve_all(&part.ouputs, &declarations),
+                    span: part.span,
+                },
+            })
+            .collect();
+        analyze(self, parts)
+    }
+}
+
+impl Definition {
+    /// `Chip::check_drivers` of the chip, a lookup table drives every output
+    pub fn check_drivers(&self, library: &Library) -> Vec<Error> {
+        match self {
+            Definition::Map(chip) => chip.check_drivers(library),
+            Definition::IO(chip) => chip.check_drivers(library),
+            Definition::Table(_) => Vec::new(),
+        }
+    }
+}
+
+// --------------------------------- utils ---------------------------------
+
+/// the bits that a part or an assignment reads and writes
+struct PartBits {
+    reads: Vec<Signal>,
+    writes: Vec<Signal>,
+    span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Finding {
+    InputDriven,
+    MultipleDrivers,
+    UndrivenOutput,
+    Undriven,
+    Dangling,
+}
+
+impl Finding {
+    fn error(&self, bits: &Signal, span: Span) -> Error {
+        let (msg, kind) = match self {
+            Finding::InputDriven => (
+                format!("input <{}> of the chip is driven by a part", bits),
+                ErrorKind::InputDriven,
+            ),
+            Finding::MultipleDrivers => (
+                format!("<{}> has more than one driver", bits),
+                ErrorKind::MultipleDrivers,
+            ),
+            Finding::UndrivenOutput => (
+                format!("output <{}> is not driven", bits),
+                ErrorKind::UndrivenSignal,
+            ),
+            Finding::Undriven => (
+                format!("<{}> is used but not driven", bits),
+                ErrorKind::UndrivenSignal,
+            ),
+            Finding::Dangling => (
+                format!("<{}> is driven but never used", bits),
+                ErrorKind::DanglingSignal,
+            ),
+        };
+        Error::msg_span(kind, &msg, span)
+    }
+}
+
+fn analyze<T>(chip: &Chip<T>, parts: Vec<PartBits>) -> Vec<Error> {
+    let inputs = flatten(chip.inputs());
+    let outputs = flatten(chip.outputs());
+
+    // every bit in the order it is first seen with the spans of its drivers and readers
+    let mut order: Vec<Signal> = inputs.iter().chain(&outputs).cloned().collect();
+    let mut drivers: HashMap<Signal, Vec<Span>> = HashMap::new();
+    let mut readers: HashMap<Signal, Vec<Span>> = HashMap::new();
+    for part in &parts {
+        for bit in part.reads.iter().chain(&part.writes) {
+            if !order.contains(bit) {
+                order.push(bit.clone());
+            }
+        }
+        for bit in &part.reads {
+            readers.entry(bit.clone()).or_default().push(part.span);
+        }
+        for bit in &part.writes {
+            drivers.entry(bit.clone()).or_default().push(part.span);
+        }
+    }
+
+    let mut findings: Vec<(Finding, Span, Vec<Signal>)> = Vec::new();
+    let mut add = |finding: Finding, span: Span, bit: &Signal| match findings
+        .iter_mut()
+        .find(|(f, s, _)| *f == finding && *s == span)
+    {
+        Some((_, _, bits)) => bits.push(bit.clone()),
+        None => findings.push((finding, span, vec![bit.clone()])),
+    };
+
+    for bit in &order {
+        if bit == "true" || bit == "false" {
+            continue;
+        }
+        let driven_by = drivers.get(bit).map_or(&[][..], Vec::as_slice);
+        let read_by = readers.get(bit).map_or(&[][..], Vec::as_slice);
+        let is_input = inputs.contains(bit);
+        let is_output = outputs.contains(bit);
+
+        if is_input {
+            for &span in driven_by {
+                add(Finding::InputDriven, span, bit);
+            }
+            continue;
+        }
+        for &span in driven_by.iter().skip(1) {
+            add(Finding::MultipleDrivers, span, bit);
+        }
+        match (driven_by.first(), read_by.first()) {
+            (None, _) if is_output => add(Finding::UndrivenOutput, chip.span, bit),
+            (None, Some(&span)) => add(Finding::Undriven, span, bit),
+            (Some(&span), None) if !is_output => add(Finding::Dangling, span, bit),
+            _ => {}
+        }
+    }
+
+    let errors = findings
+        .into_iter()
+        .flat_map(|(finding, span, bits)| {
+            group(bits)
+                .into_iter()
+                .map(move |bits| finding.error(&bits, span))
+        })
+        .collect();
+    crate::recovered(chip.span.file, (), errors)
+        .err()
+        .unwrap_or_default()
+}
@@ -0,0 +1,52 @@
+use crate::{Error, ErrorKind, Token};
+
+/// limit for the nesting of `!` and parentheses, chains like `a & b & c` are flat
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// boolean expression of a `func` or equation, variables are indices into the input list
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Var(usize),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Xor(Vec<Expr>),
+}
+
+impl Expr {
+    /// a chain of the same operator, a single operand is returned as it is
+    pub fn chain(operands: Vec<Expr>, op: fn(Vec<Expr>) -> Expr) -> Expr {
+        match <[Expr; 1]>::try_from(operands) {
+            Ok([expr]) => expr,
+            Err(operands) => op(operands),
+        }
+    }
+
+    /// evaluates the expression for every combination of `num_in` inputs,
+    /// the first input is the most significant bit of the row index
+    pub fn truth_table(&self, num_in: usize) -> Vec<bool> {
+        (0..1 << num_in)
+            .map(|row| self.eval(&|var| row >> (num_in - 1 - var) & 1 == 1))
+            .collect()
+    }
+
+    fn eval(&self, var: &dyn Fn(usize) -> bool) -> bool {
+        match self {
+            Expr::Var(i) => var(*i),
+            Expr::Not(e) => !e.eval(var),
+            Expr::And(operands) => operands.iter().all(|e| e.eval(var)),
+            Expr::Or(operands) => operands.iter().any(|e| e.eval(var)),
+            Expr::Xor(operands) => operands.iter().fold(false, |acc, e| acc ^ e.eval(var)),
+        }
+    }
+}
+
+pub(crate) fn check_depth<T: Token>(depth: usize, token: &T) -> Result<usize, Error> {
+    if depth > MAX_DEPTH {
+        return Err(
+            Error::msg_token("expression is nested too deep", token.clone())
+                .with_kind(ErrorKind::NestedTooDeep),
+        );
+    }
+    Ok(depth)
+}
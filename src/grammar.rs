@@ -0,0 +1,254 @@
+use crate::expr::{self, Expr};
+use crate::lexer::Token;
+use crate::table::Row;
+use crate::{Error, ErrorKind, Signal, MAX_NUMBER};
+use std::fmt::Debug;
+use std::iter::Peekable;
+use std::slice::Iter;
+
+// shdl and open_gal share the boolean expressions, the rows of truth tables, numbers and
+// ranges. Every dialect has its own `TokenType` and tells the parser here which of its
+// token types these are, the same way `lexer::Skip` does for the tokenizer.
+
+/// the token types of the shared grammar, the values of identifiers, numbers and tables
+/// are not compared by `==`
+pub(crate) trait Grammar: Clone + Debug + PartialEq {
+    const IDENTIFIER: Self;
+    const NUMBER: Self;
+    const BOOL_TABLE: Self;
+    const AND: Self;
+    const OR: Self;
+    const XOR: Self;
+    const NOT: Self;
+    const OPEN_P: Self;
+    const CLOSE_P: Self;
+    const OPEN_B: Self;
+    const CLOSE_B: Self;
+    const DOUBLE_DOT: Self;
+
+    fn identifier(&self) -> Option<&str>;
+    fn number(&self) -> Option<usize>;
+    fn bools(&self) -> Option<&[bool]>;
+}
+
+type TokenIter<'a, T> = Peekable<Iter<'a, Token<T>>>;
+
+// --------------------------------- expressions ---------------------------------
+
+/// precedence from low to high: `|`, `^`, `&`, `!`. `var` returns the index of an input
+/// with the token it was read from, it fails for an input that is not allowed.
+pub(crate) fn get_expr<T, F>(
+    t_iter: &mut TokenIter<T>,
+    var: &mut F,
+    depth: usize,
+) -> Result<Expr, Error>
+where
+    T: Grammar,
+    F: FnMut(Signal, &Token<T>) -> Result<usize, Error>,
+{
+    let mut operands = vec![get_xor(t_iter, var, depth)?];
+    while t_iter.next_if(|token| token.eq_type(T::OR)).is_some() {
+        operands.push(get_xor(t_iter, var, depth)?);
+    }
+    Ok(Expr::chain(operands, Expr::Or))
+}
+
+fn get_xor<T, F>(t_iter: &mut TokenIter<T>, var: &mut F, depth: usize) -> Result<Expr, Error>
+where
+    T: Grammar,
+    F: FnMut(Signal, &Token<T>) -> Result<usize, Error>,
+{
+    let mut operands = vec![get_and(t_iter, var, depth)?];
+    while t_iter.next_if(|token| token.eq_type(T::XOR)).is_some() {
+        operands.push(get_and(t_iter, var, depth)?);
+    }
+    Ok(Expr::chain(operands, Expr::Xor))
+}
+
+fn get_and<T, F>(t_iter: &mut TokenIter<T>, var: &mut F, depth: usize) -> Result<Expr, Error>
+where
+    T: Grammar,
+    F: FnMut(Signal, &Token<T>) -> Result<usize, Error>,
+{
+    let mut operands = vec![get_unary(t_iter, var, depth)?];
+    while t_iter.next_if(|token| token.eq_type(T::AND)).is_some() {
+        operands.push(get_unary(t_iter, var, depth)?);
+    }
+    Ok(Expr::chain(operands, Expr::And))
+}
+
+fn get_unary<T, F>(t_iter: &mut TokenIter<T>, var: &mut F, depth: usize) -> Result<Expr, Error>
+where
+    T: Grammar,
+    F: FnMut(Signal, &Token<T>) -> Result<usize, Error>,
+{
+    // a token that can not start an expression is not consumed so that `;` ends the statement
+    let token = match t_iter.next_if(|token| starts_expr(&token.token_type)) {
+        Some(token) => token,
+        None => {
+            return Err(match t_iter.peek() {
+                Some(&token) => Error::msg_token(
+                    &format!("expected expression but got <{:?}>", token.token_type),
+                    token.clone(),
+                ),
+                None => Error::msg(
+                    ErrorKind::UnexpectedEof,
+                    "unexpected end of file expected expression",
+                ),
+            })
+        }
+    };
+
+    if let Some(name) = token.token_type.identifier() {
+        let input = get_variable(t_iter, name)?;
+        return Ok(Expr::Var(var(input, token)?));
+    }
+    let depth = expr::check_depth(depth + 1, token)?;
+    if token.eq_type(T::NOT) {
+        Ok(Expr::Not(Box::new(get_unary(t_iter, var, depth)?)))
+    } else {
+        let expr = get_expr(t_iter, var, depth)?;
+        Error::expect(t_iter.next(), T::CLOSE_P)?;
+        Ok(expr)
+    }
+}
+
+fn starts_expr<T: Grammar>(token_type: &T) -> bool {
+    *token_type == T::NOT || *token_type == T::OPEN_P || *token_type == T::IDENTIFIER
+}
+
+/// a variable of a function is one bit like `a` or `a[3]`, the identifier is already read
+pub(crate) fn get_variable<T: Grammar>(
+    t_iter: &mut TokenIter<T>,
+    identifier: &str,
+) -> Result<Signal, Error> {
+    if t_iter.next_if(|token| token.eq_type(T::OPEN_B)).is_none() {
+        return Ok(Signal::new(identifier));
+    }
+    let index = get_num(t_iter.next())?;
+    Error::expect(t_iter.next(), T::CLOSE_B)?;
+    Ok(Signal::index(identifier, index))
+}
+
+// --------------------------------- tables ---------------------------------
+
+/// a row is either `00 1` or `001`, the first `num_in` values are the inputs
+pub(crate) fn get_rows<T: Grammar>(
+    t_iter: &mut TokenIter<T>,
+    num_in: usize,
+) -> Result<Vec<Row<Token<T>>>, Error> {
+    let mut rows = Vec::new();
+
+    while let Some(&token) = t_iter.peek() {
+        if !token.eq_type(T::BOOL_TABLE) {
+            break;
+        }
+        let mut inputs = get_bools(t_iter.next())?;
+        let outputs = if inputs.len() > num_in {
+            inputs.split_off(num_in)
+        } else {
+            get_bools(t_iter.next())?
+        };
+        rows.push(Row {
+            token: token.clone(),
+            inputs,
+            outputs,
+        });
+    }
+
+    Ok(rows)
+}
+
+/// the values of a `count` table with the token of the first one
+pub(crate) fn get_count<T: Grammar>(
+    t_iter: &mut TokenIter<T>,
+) -> Result<(Token<T>, Vec<bool>), Error> {
+    let first = t_iter.peek().map(|&token| token.clone());
+    let mut values = get_bools(t_iter.next())?;
+
+    while let Some(&token) = t_iter.peek() {
+        if !token.eq_type(T::BOOL_TABLE) {
+            break;
+        }
+        values.append(&mut get_bools(t_iter.next())?);
+    }
+
+    match first {
+        Some(token) => Ok((token, values)),
+        None => Err(Error::msg(
+            ErrorKind::UnexpectedEof,
+            "unexpected end of file expected count table",
+        )),
+    }
+}
+
+pub(crate) fn get_bools<T: Grammar>(token: Option<&Token<T>>) -> Result<Vec<bool>, Error> {
+    match Error::expect(token, T::BOOL_TABLE)?.bools() {
+        Some(bools) => Ok(bools.to_vec()),
+        None => Err(Error::msg_option("expected bool table", token)),
+    }
+}
+
+pub(crate) fn get_bit<T: Grammar>(token: Option<&Token<T>>) -> Result<bool, Error> {
+    match (get_bools(token)?.as_slice(), token) {
+        ([bit], _) => Ok(*bit),
+        (_, Some(token)) => Err(Error::msg_token("expected <0> or <1>", token.clone())),
+        (_, None) => Err(Error::msg(
+            ErrorKind::UnexpectedEof,
+            "unexpected end of file expected <0> or <1>",
+        )),
+    }
+}
+
+// --------------------------------- utils ---------------------------------
+
+/// `[start..end]` with both ends included, the end can not be before the start, or
+/// `[index]` for a single value
+pub(crate) fn get_range<T: Grammar>(t_iter: &mut TokenIter<T>) -> Result<(usize, usize), Error> {
+    Error::expect(t_iter.next(), T::OPEN_B)?;
+    let start = get_num(t_iter.next())?;
+    if t_iter.next_if(|token| token.eq_type(T::CLOSE_B)).is_some() {
+        return Ok((start, start));
+    }
+    Error::expect(t_iter.next(), T::DOUBLE_DOT)?;
+    let end_token = t_iter.peek().copied();
+    let end = get_num(t_iter.next())?;
+    Error::expect(t_iter.next(), T::CLOSE_B)?;
+    if end < start {
+        let msg = format!("invalid range <{}..{}>", start, end);
+        return Err(Error::msg_option(&msg, end_token).with_kind(ErrorKind::InvalidRange));
+    }
+    Ok((start, end))
+}
+
+pub(crate) fn get_num<T: Grammar>(token: Option<&Token<T>>) -> Result<usize, Error> {
+    if let Some(token) = token {
+        // numbers that only contain 0 and 1 are lexed as a bool table
+        if let Some(bools) = token.token_type.bools() {
+            return bools
+                .iter()
+                .try_fold(0usize, |num, &bit| {
+                    num.checked_mul(10)?.checked_add(bit as usize)
+                })
+                .filter(|&num| num <= MAX_NUMBER)
+                .ok_or_else(|| {
+                    Error::msg_token("number is too large", token.clone())
+                        .with_kind(ErrorKind::NumberTooLarge)
+                });
+        }
+    }
+    match Error::expect(token, T::NUMBER)?.number() {
+        Some(num) if num <= MAX_NUMBER => Ok(num),
+        _ => {
+            Err(Error::msg_option("number is too large", token)
+                .with_kind(ErrorKind::NumberTooLarge))
+        }
+    }
+}
+
+pub(crate) fn get_identifier<T: Grammar>(token: Option<&Token<T>>) -> Result<String, Error> {
+    match Error::expect(token, T::IDENTIFIER)?.identifier() {
+        Some(name) => Ok(name.to_string()),
+        None => Err(Error::msg_option("expected identifier", token)),
+    }
+}
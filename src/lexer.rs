@@ -0,0 +1,124 @@
+use crate::Error;
+use logos::{Lexer, Logos};
+use std::fmt::Debug;
+use std::iter::Peekable;
+use std::slice::Iter;
+
+// Every dialect has its own `TokenType` and shares the tokens, the tokenizer, the
+// callbacks for whitespace and comments and the recovery after an error.
+
+/// a token with its position in the source
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token<T> {
+    pub(crate) index: usize,
+    pub(crate) line: usize,
+    pub(crate) len: usize,
+    pub(crate) token_type: T,
+}
+
+impl<T: Clone + PartialEq + Debug> crate::Token for Token<T> {
+    type TokenType = T;
+    fn line(&self) -> usize {
+        self.line
+    }
+    fn index(&self) -> usize {
+        self.index
+    }
+    fn len(&self) -> usize {
+        self.len
+    }
+    fn token_type(&self) -> Self::TokenType {
+        self.token_type.clone()
+    }
+}
+
+impl<T: PartialEq> Token<T> {
+    pub(crate) fn eq_type(&self, token_type: T) -> bool {
+        self.token_type == token_type
+    }
+}
+
+/// the token types that the tokenizer does not return
+pub(crate) trait Skip {
+    /// the newlines of whitespace or a comment, `None` for every other token
+    fn newlines(&self) -> Option<usize>;
+
+    /// text that is not part of any token
+    fn is_unknown(&self) -> bool;
+}
+
+/// the tokens and an `InvalidCharacter` error for every unknown text, the line of a
+/// token counts the newlines before it
+pub(crate) fn tokenize<'a, T>(code: &'a str) -> (Vec<Token<T>>, Vec<Error>)
+where
+    T: Logos<'a, Source = str, Extras = ()> + Skip,
+{
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+    let mut line = 0;
+    let mut lex = T::lexer(code);
+
+    while let Some(token_type) = lex.next() {
+        if let Some(newlines) = token_type.newlines() {
+            line += newlines;
+        } else if token_type.is_unknown() {
+            errors.push(Error::invalid_character(
+                lex.slice(),
+                lex.span().start,
+                line,
+            ));
+        } else {
+            tokens.push(Token {
+                index: lex.span().start,
+                line,
+                len: lex.span().len(),
+                token_type,
+            });
+        }
+    }
+
+    (tokens, errors)
+}
+
+/// whitespace or a line comment
+pub(crate) fn ignore<'a, T: Logos<'a, Source = str>>(lex: &mut Lexer<'a, T>) -> usize {
+    lex.slice().matches('\n').count()
+}
+
+/// a block comment over several lines, an unterminated comment is lexed as unknown
+pub(crate) fn block_comment<'a, T: Logos<'a, Source = str>>(
+    lex: &mut Lexer<'a, T>,
+) -> Option<usize> {
+    let end = lex.remainder().find("*/")?;
+    lex.bump(end + 2);
+    Some(lex.slice().matches('\n').count())
+}
+
+/// a decimal number, a number that does not fit is `usize::MAX` so that the parser
+/// reports it as too large
+pub(crate) fn number<'a, T: Logos<'a, Source = str>>(lex: &mut Lexer<'a, T>) -> usize {
+    lex.slice().parse().unwrap_or(usize::MAX)
+}
+
+/// a row of a truth table like `0110`
+pub(crate) fn table<'a, T: Logos<'a, Source = str>>(lex: &mut Lexer<'a, T>) -> Vec<bool> {
+    lex.slice().chars().map(|c| c == '1').collect()
+}
+
+/// the token after the next one without advancing the iterator
+pub(crate) fn peek_second<'a, T>(t_iter: &Peekable<Iter<'a, Token<T>>>) -> Option<&'a Token<T>> {
+    t_iter.clone().nth(1)
+}
+
+/// skips tokens up to one of `keep`, which is not consumed, or one of `end`, which is
+pub(crate) fn skip<T: PartialEq>(t_iter: &mut Peekable<Iter<Token<T>>>, keep: &[T], end: &[T]) {
+    while let Some(&token) = t_iter.peek() {
+        if keep.contains(&token.token_type) {
+            break;
+        }
+        t_iter.next();
+        if end.contains(&token.token_type) {
+            break;
+        }
+    }
+}
@@ -1,32 +1,73 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
+use std::ops::Range;
 
+pub mod builtin;
 pub mod comphdl;
 pub mod n2t_hdl;
 pub mod open_gal;
 pub mod shdl;
 
-#[derive(Debug, Clone, PartialEq)]
+mod builder;
+mod check;
+mod convert;
+mod detect;
+mod drivers;
+mod expr;
+mod grammar;
+mod lexer;
+mod library;
+mod loops;
+mod netlist;
+mod project;
+mod signal;
+mod span;
+mod table;
+
+pub use builder::{ChipBuilder, LookupTableBuilder, OGalBuilder};
+pub use detect::{detect, parse_any, Dialect, HdlParse};
+pub use library::{Library, Signature};
+pub use netlist::{Cell, Direction, Instance, Module, Net, NetId, Netlist, Pin, Port, Register};
+pub use project::{Definition, Project};
+pub use signal::Signal;
+pub use span::{FileId, SourceMap, Span};
+
+/// largest number allowed in bus widths, ranges and pin numbers so that inputs like
+/// `a[99999999]` return an error instead of allocating every bit
+pub(crate) const MAX_NUMBER: usize = 4096;
+
+/// the span is only a location and is ignored by `==`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chip<T> {
     name: String,
-    inputs: Vec<String>,
-    outputs: Vec<String>,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
     parts: Vec<T>,
+    builtin: Option<String>,
+    clocked: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    span: Span,
+}
+
+impl<T: PartialEq> PartialEq for Chip<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.inputs == other.inputs
+            && self.outputs == other.outputs
+            && self.parts == other.parts
+            && self.builtin == other.builtin
+            && self.clocked == other.clocked
+    }
 }
 
 impl<T> Chip<T> {
     pub fn new(name: &str, inputs: Vec<&str>, outputs: Vec<&str>, parts: Vec<T>) -> Self {
-        Self {
-            name: name.to_string(),
-            inputs: inputs
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
-            outputs: outputs
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
+        Self::new_signals(
+            name,
+            inputs.into_iter().map(Signal::new).collect(),
+            outputs.into_iter().map(Signal::new).collect(),
             parts,
-        }
+        )
     }
 
     pub fn new_string(
@@ -37,105 +78,418 @@ impl<T> Chip<T> {
     ) -> Self {
         Self {
             name,
+            inputs: inputs.into_iter().map(Signal::new_string).collect(),
+            outputs: outputs.into_iter().map(Signal::new_string).collect(),
+            parts,
+            builtin: None,
+            clocked: Vec::new(),
+            span: Span::default(),
+        }
+    }
+
+    /// like `new` with buses as inputs and outputs
+    pub fn new_signals(
+        name: &str,
+        inputs: Vec<Signal>,
+        outputs: Vec<Signal>,
+        parts: Vec<T>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
             inputs,
             outputs,
             parts,
+            builtin: None,
+            clocked: Vec::new(),
+            span: Span::default(),
         }
     }
+
+    /// chip without parts that is implemented by the simulator (`BUILTIN` in n2t_hdl),
+    /// `clocked` are the inputs that only change the chip state on a clock edge
+    pub fn new_builtin(
+        name: &str,
+        inputs: Vec<&str>,
+        outputs: Vec<&str>,
+        builtin: &str,
+        clocked: Vec<&str>,
+    ) -> Self {
+        Self {
+            builtin: Some(builtin.to_string()),
+            clocked: clocked
+                .iter()
+                .map(|&s| -> String { s.to_string() })
+                .collect(),
+            ..Self::new(name, inputs, outputs, Vec::new())
+        }
+    }
+
+    pub fn builder(name: &str) -> ChipBuilder<T> {
+        ChipBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn inputs(&self) -> &[Signal] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[Signal] {
+        &self.outputs
+    }
+
+    /// all inputs followed by all outputs
+    pub fn pins(&self) -> impl Iterator<Item = &Signal> {
+        self.inputs.iter().chain(&self.outputs)
+    }
+
+    /// the declared width of an input or output
+    pub fn width(&self, pin: &str) -> Option<usize> {
+        self.pins()
+            .find(|signal| signal.name() == pin)
+            .map(Signal::width)
+    }
+
+    /// every input bit followed by every output bit
+    pub fn bits(&self) -> Vec<Signal> {
+        signal::flatten(&self.inputs)
+            .into_iter()
+            .chain(signal::flatten(&self.outputs))
+            .collect()
+    }
+
+    pub fn parts(&self) -> &[T] {
+        &self.parts
+    }
+
+    pub fn builtin(&self) -> Option<&str> {
+        self.builtin.as_deref()
+    }
+
+    pub fn clocked(&self) -> &[String] {
+        &self.clocked
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// the span is only a location and is ignored by `==`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LookupTable {
     name: String,
-    inputs: Vec<String>,
-    output: Vec<String>,
+    inputs: Vec<Signal>,
+    output: Vec<Signal>,
     table: Vec<Vec<bool>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    span: Span,
+}
+
+impl PartialEq for LookupTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.inputs == other.inputs
+            && self.output == other.output
+            && self.table == other.table
+    }
 }
 
 impl LookupTable {
     pub fn new(name: &str, inputs: Vec<&str>, output: Vec<&str>, table: Vec<Vec<bool>>) -> Self {
+        Self::new_signals(
+            name,
+            inputs.into_iter().map(Signal::new).collect(),
+            output.into_iter().map(Signal::new).collect(),
+            table,
+        )
+    }
+
+    pub fn new_string(
+        name: String,
+        inputs: Vec<String>,
+        output: Vec<String>,
+        table: Vec<Vec<bool>>,
+    ) -> Self {
+        Self {
+            name,
+            inputs: inputs.into_iter().map(Signal::new_string).collect(),
+            output: output.into_iter().map(Signal::new_string).collect(),
+            table,
+            span: Span::default(),
+        }
+    }
+
+    /// like `new` with buses, every bit of a bus is its own input or output
+    pub fn new_signals(
+        name: &str,
+        inputs: Vec<Signal>,
+        output: Vec<Signal>,
+        table: Vec<Vec<bool>>,
+    ) -> Self {
         Self {
             name: name.to_string(),
-            inputs: inputs
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
-            output: output
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
+            inputs: signal::flatten(&inputs),
+            output: signal::flatten(&output),
             table,
+            span: Span::default(),
         }
     }
+
+    pub fn builder(name: &str) -> LookupTableBuilder {
+        LookupTableBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// every input bit, the first one is the highest bit of the row index
+    pub fn inputs(&self) -> &[Signal] {
+        &self.inputs
+    }
+
+    /// every output bit in the order of the columns
+    pub fn outputs(&self) -> &[Signal] {
+        &self.output
+    }
+
+    /// one column for every output, the row index has the first input as its highest bit
+    pub fn table(&self) -> &[Vec<bool>] {
+        &self.table
+    }
+
+    /// the value of every output for the input values
+    pub fn eval(&self, inputs: &[bool]) -> Option<Vec<bool>> {
+        if inputs.len() != self.inputs.len() {
+            return None;
+        }
+        let row = inputs
+            .iter()
+            .fold(0, |index, &bit| (index << 1) | bit as usize);
+        self.table
+            .iter()
+            .map(|column| column.get(row).copied())
+            .collect()
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// the span is only a location and is ignored by `==`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComponentMap {
-    var_map: Vec<(String, String)>,
+    var_map: Vec<(Signal, Signal)>,
     name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    span: Span,
+}
+
+impl PartialEq for ComponentMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.var_map == other.var_map && self.name == other.name
+    }
 }
 
 impl ComponentMap {
     pub fn new(var_map: Vec<(&str, &str)>, name: &str) -> Self {
+        Self::new_signals(
+            var_map
+                .into_iter()
+                .map(|(pin, signal)| (Signal::new(pin), Signal::new(signal)))
+                .collect(),
+            name,
+        )
+    }
+
+    pub fn new_string(var_map: Vec<(String, String)>, name: String) -> Self {
         Self {
             var_map: var_map
-                .iter()
-                .map(|&(s1, s2)| -> (String, String) { (s1.to_string(), s2.to_string()) })
+                .into_iter()
+                .map(|(pin, signal)| (Signal::new_string(pin), Signal::new_string(signal)))
                 .collect(),
+            name,
+            span: Span::default(),
+        }
+    }
+
+    /// like `new` with sub buses like `a[0..3]=x` on either side
+    pub fn new_signals(var_map: Vec<(Signal, Signal)>, name: &str) -> Self {
+        Self {
+            var_map,
             name: name.to_string(),
+            span: Span::default(),
         }
     }
 
-    pub fn new_string(var_map: Vec<(String, String)>, name: String) -> Self {
-        Self { var_map, name }
+    /// name of the chip that is used
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn var_map(&self) -> &[(Signal, Signal)] {
+        &self.var_map
+    }
+
+    /// every connection as `(pin, signal)`
+    pub fn pins(&self) -> impl Iterator<Item = (&Signal, &Signal)> {
+        self.var_map.iter().map(|(pin, signal)| (pin, signal))
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// the span is only a location and is ignored by `==`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComponentIO {
-    inputs: Vec<String>,
-    ouputs: Vec<String>,
+    inputs: Vec<Signal>,
+    ouputs: Vec<Signal>,
     name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    span: Span,
+}
+
+impl PartialEq for ComponentIO {
+    fn eq(&self, other: &Self) -> bool {
+        self.inputs == other.inputs && self.ouputs == other.ouputs && self.name == other.name
+    }
 }
 
 impl ComponentIO {
     pub fn new(inputs: Vec<&str>, ouputs: Vec<&str>, name: &str) -> Self {
+        Self::new_signals(
+            inputs.into_iter().map(Signal::new).collect(),
+            ouputs.into_iter().map(Signal::new).collect(),
+            name,
+        )
+    }
+
+    pub fn new_string(inputs: Vec<String>, ouputs: Vec<String>, name: String) -> Self {
         Self {
-            inputs: inputs
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
-            ouputs: ouputs
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
+            inputs: inputs.into_iter().map(Signal::new_string).collect(),
+            ouputs: ouputs.into_iter().map(Signal::new_string).collect(),
+            name,
+            span: Span::default(),
+        }
+    }
+
+    /// like `new` with sub buses like `a[0..3]` as signals
+    pub fn new_signals(inputs: Vec<Signal>, ouputs: Vec<Signal>, name: &str) -> Self {
+        Self {
+            inputs,
+            ouputs,
             name: name.to_string(),
+            span: Span::default(),
         }
     }
+
+    /// name of the chip that is used, empty for a plain assignment like `a = b;`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_assignment(&self) -> bool {
+        self.name.is_empty()
+    }
+
+    pub fn inputs(&self) -> &[Signal] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[Signal] {
+        &self.ouputs
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// a part with named inputs and outputs, the span is only a location and is ignored by `==`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Component {
-    inputs: Vec<(String, String)>,
-    ouputs: Vec<(String, String)>,
+    inputs: Vec<(Signal, Signal)>,
+    ouputs: Vec<(Signal, Signal)>,
     name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    span: Span,
+}
+
+impl PartialEq for Component {
+    fn eq(&self, other: &Self) -> bool {
+        self.inputs == other.inputs && self.ouputs == other.ouputs && self.name == other.name
+    }
 }
 
 impl Component {
     pub fn new(inputs: Vec<(&str, &str)>, ouputs: Vec<(&str, &str)>, name: &str) -> Self {
+        let signals = |pins: Vec<(&str, &str)>| {
+            pins.into_iter()
+                .map(|(pin, signal)| (Signal::new(pin), Signal::new(signal)))
+                .collect()
+        };
+        Self::new_signals(signals(inputs), signals(ouputs), name)
+    }
+
+    pub fn new_string(
+        inputs: Vec<(String, String)>,
+        ouputs: Vec<(String, String)>,
+        name: String,
+    ) -> Self {
+        let signals = |pins: Vec<(String, String)>| {
+            pins.into_iter()
+                .map(|(pin, signal)| (Signal::new_string(pin), Signal::new_string(signal)))
+                .collect()
+        };
         Self {
-            inputs: inputs
-                .iter()
-                .map(|&(s1, s2)| -> (String, String) { (s1.to_string(), s2.to_string()) })
-                .collect(),
+            inputs: signals(inputs),
+            ouputs: signals(ouputs),
+            name,
+            span: Span::default(),
+        }
+    }
 
-            ouputs: ouputs
-                .iter()
-                .map(|&(s1, s2)| -> (String, String) { (s1.to_string(), s2.to_string()) })
-                .collect(),
+    /// like `new` with sub buses like `a[0..3]=x` on either side
+    pub fn new_signals(
+        inputs: Vec<(Signal, Signal)>,
+        ouputs: Vec<(Signal, Signal)>,
+        name: &str,
+    ) -> Self {
+        Self {
+            inputs,
+            ouputs,
             name: name.to_string(),
+            span: Span::default(),
         }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// every input as `(pin, signal)`
+    pub fn inputs(&self) -> &[(Signal, Signal)] {
+        &self.inputs
+    }
+
+    /// every output as `(pin, signal)`
+    pub fn outputs(&self) -> &[(Signal, Signal)] {
+        &self.ouputs
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
 pub trait Token: Clone {
@@ -143,11 +497,79 @@ pub trait Token: Clone {
     fn line(&self) -> usize;
     fn index(&self) -> usize;
     fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     fn token_type(&self) -> Self::TokenType;
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorKind {
+    /// the token does not fit the grammar at this position
+    UnexpectedToken,
+    /// the file ended in the middle of a chip or statement
+    UnexpectedEof,
+    /// the lexer found text that is not part of any token, like `@` or `é`
+    InvalidCharacter(String),
+    /// a number above the limit for bus widths, ranges and pin numbers
+    NumberTooLarge,
+    /// a range like `a[4..2]` where the end is before the start
+    InvalidRange,
+    /// both sides of a connection have a different number of bits
+    WidthMismatch,
+    /// a pin number or name that is assigned more than once
+    DuplicatePin,
+    /// an output of a `func` section that is defined more than once
+    DuplicateOutput,
+    /// an output without a definition
+    MissingOutput,
+    /// a name that is neither an input nor an output of the chip
+    UnknownSignal,
+    /// a part that uses a chip which is not defined
+    UnknownChip,
+    /// a truth table with missing, duplicate or conflicting rows
+    InvalidTable,
+    /// a truth table with too many inputs or outputs
+    TableTooLarge,
+    /// an expression with too many nested `!` or parentheses
+    NestedTooDeep,
+    /// a chip or pin name that is not a valid identifier
+    InvalidName,
+    /// a builtin chip that also has parts
+    BuiltinWithParts,
+    /// `parse_any` found no keyword that starts a statement of one of the dialects
+    UnknownDialect,
+    /// `parse_any` found keywords of more than one dialect
+    AmbiguousDialect,
+    /// a part with more or fewer signals than the chip it uses has pins
+    ArityMismatch,
+    /// a part that can not be written in the target dialect
+    Unsupported,
+    /// a chip that is defined in more than one place of a project
+    DuplicateChip,
+    /// a chip that uses itself as a part, directly or through other chips
+    CircularDependency,
+    /// an input of a part that is not connected to a signal
+    UnconnectedInput,
+    /// a signal that is used but has no driver, or an output of the chip without one
+    UndrivenSignal,
+    /// a signal that is driven by more than one part
+    MultipleDrivers,
+    /// an input of the chip that is driven by a part
+    InputDriven,
+    /// a warning for an internal signal that is driven but never used
+    DanglingSignal,
+    /// parts that depend on each other without a clocked input in between
+    CombinationalLoop,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Error {
+    kind: ErrorKind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    file: FileId,
     line: Option<usize>,
     index: Option<usize>,
     len: Option<usize>,
@@ -162,6 +584,8 @@ impl Error {
                 Ok(token.token_type())
             } else {
                 Err(Self {
+                    kind: ErrorKind::UnexpectedToken,
+                    file: FileId::default(),
                     line: Some(token.line()),
                     index: Some(token.index()),
                     len: Some(token.len()),
@@ -174,16 +598,21 @@ impl Error {
             }
         } else {
             Err(Self {
+                kind: ErrorKind::UnexpectedEof,
+                file: FileId::default(),
                 line: None,
                 index: None,
                 len: None,
-                msg: format!("unexpected end of file expected token <{:?}> ", expected),
+                msg: format!("unexpected end of file expected token <{:?}>", expected),
             })
         }
     }
 
+    /// the kind is `UnexpectedToken` unless it is changed with `with_kind`
     pub fn msg_token<T: Token>(msg: &str, token: T) -> Self {
         Self {
+            kind: ErrorKind::UnexpectedToken,
+            file: FileId::default(),
             line: Some(token.line()),
             index: Some(token.index()),
             len: Some(token.len()),
@@ -193,6 +622,8 @@ impl Error {
 
     pub fn msg_len<T: Token>(msg: &str, token: T, len: usize) -> Self {
         Self {
+            kind: ErrorKind::UnexpectedToken,
+            file: FileId::default(),
             line: Some(token.line()),
             index: Some(token.index()),
             len: Some(len),
@@ -200,12 +631,277 @@ impl Error {
         }
     }
 
-    pub fn msg(msg: &str) -> Self {
+    /// like `msg_token` but without a position at the end of the file
+    pub fn msg_option<T: Token>(msg: &str, token: Option<&T>) -> Self {
+        match token {
+            Some(token) => Self::msg_token(msg, token.clone()),
+            None => Self::msg(ErrorKind::UnexpectedEof, msg),
+        }
+    }
+
+    /// `text` starts at the byte `index` of the source
+    pub fn invalid_character(text: &str, index: usize, line: usize) -> Self {
+        let msg = if text.starts_with("/*") {
+            "unterminated block comment".to_string()
+        } else {
+            format!("invalid character <{}>", text)
+        };
         Self {
+            kind: ErrorKind::InvalidCharacter(text.to_string()),
+            file: FileId::default(),
+            line: Some(line),
+            index: Some(index),
+            len: Some(text.len()),
+            msg,
+        }
+    }
+
+    /// an error without a position in the source
+    pub fn msg(kind: ErrorKind, msg: &str) -> Self {
+        Self {
+            kind,
+            file: FileId::default(),
             line: None,
             index: None,
             len: None,
             msg: msg.to_string(),
         }
     }
+
+    /// an error at a span of the parse result, the line is unknown without the source
+    pub fn msg_span(kind: ErrorKind, msg: &str, span: Span) -> Self {
+        Self {
+            file: span.file,
+            index: Some(span.start),
+            len: Some(span.end - span.start),
+            ..Self::msg(kind, msg)
+        }
+    }
+
+    pub fn with_kind(self, kind: ErrorKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// the file of the error, it is set by `parse_file` and `msg_span`
+    pub fn with_file(self, file: FileId) -> Self {
+        Self { file, ..self }
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    /// a mistake that does not change the behaviour of the chip, like a signal that is
+    /// never used
+    pub fn is_warning(&self) -> bool {
+        matches!(self.kind, ErrorKind::DanglingSignal)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.msg
+    }
+
+    /// byte range of the error in the source, `None` at the end of the file
+    pub fn span(&self) -> Option<Range<usize>> {
+        match (self.index, self.len) {
+            (Some(index), Some(len)) => Some(index..index + len),
+            _ => None,
+        }
+    }
+}
+
+/// the result of a `parse_file`, the errors are sorted by their position
+/// and errors at the end of the file come last
+pub(crate) fn recovered<T>(file: FileId, value: T, errors: Vec<Error>) -> Result<T, Vec<Error>> {
+    if errors.is_empty() {
+        return Ok(value);
+    }
+    let mut errors: Vec<_> = errors.into_iter().map(|err| err.with_file(file)).collect();
+    errors.sort_by_key(|err| err.index.unwrap_or(usize::MAX));
+    Err(errors)
+}
+
+/// only the first error for the `parse` functions
+pub(crate) fn first_error<T>(result: Result<T, Vec<Error>>) -> Result<T, Error> {
+    result.map_err(|errors| {
+        errors.into_iter().next().unwrap_or_else(|| {
+            Error::msg(ErrorKind::UnexpectedEof, "parse failed without an error")
+        })
+    })
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line + 1, self.msg),
+            None => write!(f, "{}", self.msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ErrorKind {
+    fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::UnexpectedToken => "unexpected_token",
+            ErrorKind::UnexpectedEof => "unexpected_eof",
+            ErrorKind::InvalidCharacter(_) => "invalid_character",
+            ErrorKind::NumberTooLarge => "number_too_large",
+            ErrorKind::InvalidRange => "invalid_range",
+            ErrorKind::WidthMismatch => "width_mismatch",
+            ErrorKind::DuplicatePin => "duplicate_pin",
+            ErrorKind::DuplicateOutput => "duplicate_output",
+            ErrorKind::MissingOutput => "missing_output",
+            ErrorKind::UnknownSignal => "unknown_signal",
+            ErrorKind::UnknownChip => "unknown_chip",
+            ErrorKind::InvalidTable => "invalid_table",
+            ErrorKind::TableTooLarge => "table_too_large",
+            ErrorKind::NestedTooDeep => "nested_too_deep",
+            ErrorKind::InvalidName => "invalid_name",
+            ErrorKind::BuiltinWithParts => "builtin_with_parts",
+            ErrorKind::UnknownDialect => "unknown_dialect",
+            ErrorKind::AmbiguousDialect => "ambiguous_dialect",
+            ErrorKind::ArityMismatch => "arity_mismatch",
+            ErrorKind::Unsupported => "unsupported",
+            ErrorKind::DuplicateChip => "duplicate_chip",
+            ErrorKind::CircularDependency => "circular_dependency",
+            ErrorKind::UnconnectedInput => "unconnected_input",
+            ErrorKind::UndrivenSignal => "undriven_signal",
+            ErrorKind::MultipleDrivers => "multiple_drivers",
+            ErrorKind::InputDriven => "input_driven",
+            ErrorKind::DanglingSignal => "dangling_signal",
+            ErrorKind::CombinationalLoop => "combinational_loop",
+        }
+    }
+}
+
+// --------------------------------- diagnostics ---------------------------------
+
+impl Error {
+    /// rustc style view of the error, `source` has to be the code that was parsed.
+    /// Warnings start with `warning:`.
+    ///
+    /// ```text
+    /// error: invalid character <@>
+    ///  --> not.hdl:3:16
+    ///   |
+    /// 3 |         OUT out@;
+    ///   |                ^
+    /// ```
+    pub fn render(&self, file: &str, source: &str) -> String {
+        let pos = Position::new(source, self.span());
+        let number = pos.line.to_string();
+        let pad = " ".repeat(number.len());
+        // keep tabs so that the caret lines up with the source line
+        let indent: String = pos.text[..pos.offset]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        let level = if self.is_warning() {
+            "warning"
+        } else {
+            "error"
+        };
+
+        format!(
+            "{}: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            level,
+            self.msg,
+            pad,
+            file,
+            pos.line,
+            pos.column,
+            pad,
+            number,
+            pos.text,
+            pad,
+            indent,
+            "^".repeat(pos.width)
+        )
+    }
+
+    /// the error as a single json object for editors, `start` and `end` are byte offsets
+    /// and `null` for errors at the end of the file
+    pub fn to_json(&self, file: &str, source: &str) -> String {
+        let span = self.span();
+        let pos = Position::new(source, span.clone());
+        let (start, end) = match span {
+            Some(span) => (span.start.to_string(), span.end.to_string()),
+            None => ("null".to_string(), "null".to_string()),
+        };
+
+        format!(
+            "{{\"file\":{},\"kind\":\"{}\",\"message\":{},\"line\":{},\"column\":{},\"start\":{},\"end\":{}}}",
+            json_string(file),
+            self.kind.name(),
+            json_string(&self.msg),
+            pos.line,
+            pos.column,
+            start,
+            end
+        )
+    }
+}
+
+/// 1-based line and column of a span, errors without a span point at the end of the source
+struct Position<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+    /// byte offset of the column in `text`
+    offset: usize,
+    /// number of carets, at least one and never past the end of the line
+    width: usize,
+}
+
+impl<'a> Position<'a> {
+    fn new(source: &'a str, span: Option<Range<usize>>) -> Self {
+        let mut start = span.as_ref().map_or(source.len(), |span| span.start);
+        start = start.min(source.len());
+        while !source.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let end = span.map_or(start, |span| span.end).clamp(start, line_end);
+        let text = source[line_start..line_end].trim_end_matches('\r');
+
+        Self {
+            line: source[..start].matches('\n').count() + 1,
+            column: source[line_start..start].chars().count() + 1,
+            text,
+            // the `\r` of a CRLF line is not part of `text`
+            offset: (start - line_start).min(text.len()),
+            width: source
+                .get(start..end)
+                .map_or(0, |s| s.chars().count())
+                .max(1),
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut result = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
 }
@@ -0,0 +1,176 @@
+use crate::{signal, Chip, LookupTable, Signal};
+use std::collections::BTreeMap;
+
+/// the name and pins of a chip, everything that is needed to use it as a part
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Signature {
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    clocked: Vec<String>,
+}
+
+impl Signature {
+    pub fn new(name: &str, inputs: Vec<&str>, outputs: Vec<&str>) -> Self {
+        Self::new_signals(
+            name,
+            inputs.into_iter().map(Signal::new).collect(),
+            outputs.into_iter().map(Signal::new).collect(),
+        )
+    }
+
+    pub fn new_string(name: String, inputs: Vec<String>, outputs: Vec<String>) -> Self {
+        Self {
+            name,
+            inputs: inputs.into_iter().map(Signal::new_string).collect(),
+            outputs: outputs.into_iter().map(Signal::new_string).collect(),
+            clocked: Vec::new(),
+        }
+    }
+
+    /// like `new` with buses like `Signal::bus("a", 16)` as pins
+    pub fn new_signals(name: &str, inputs: Vec<Signal>, outputs: Vec<Signal>) -> Self {
+        Self {
+            name: name.to_string(),
+            inputs,
+            outputs,
+            clocked: Vec::new(),
+        }
+    }
+
+    /// `clocked` are the inputs that only change the outputs on a clock edge like
+    /// `CLOCKED` in n2t_hdl
+    pub fn with_clocked(self, clocked: &[&str]) -> Self {
+        Self {
+            clocked: clocked.iter().map(|pin| pin.to_string()).collect(),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn inputs(&self) -> &[Signal] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[Signal] {
+        &self.outputs
+    }
+
+    pub fn clocked(&self) -> &[String] {
+        &self.clocked
+    }
+
+    /// an input in `clocked`, see `with_clocked`
+    pub fn is_clocked(&self, pin: &str) -> bool {
+        self.clocked.iter().any(|clocked| clocked == pin)
+    }
+
+    /// a chip with state, at least one input is clocked
+    pub fn is_sequential(&self) -> bool {
+        self.inputs
+            .iter()
+            .any(|input| self.is_clocked(input.name()))
+    }
+
+    pub fn has_input(&self, pin: &str) -> bool {
+        self.inputs.iter().any(|input| input.name() == pin)
+    }
+
+    pub fn has_output(&self, pin: &str) -> bool {
+        self.outputs.iter().any(|output| output.name() == pin)
+    }
+
+    /// the declaration of an input or output
+    pub fn pin(&self, pin: &str) -> Option<&Signal> {
+        self.inputs
+            .iter()
+            .chain(&self.outputs)
+            .find(|signal| signal.name() == pin)
+    }
+}
+
+impl<T> From<&Chip<T>> for Signature {
+    fn from(chip: &Chip<T>) -> Self {
+        Self {
+            clocked: chip.clocked().to_vec(),
+            ..Self::new_signals(chip.name(), chip.inputs().to_vec(), chip.outputs().to_vec())
+        }
+    }
+}
+
+/// the bits of a bus are merged back into one pin like `a[0..1]`
+impl From<&LookupTable> for Signature {
+    fn from(lookup_table: &LookupTable) -> Self {
+        Self::new_signals(
+            lookup_table.name(),
+            signal::group(lookup_table.inputs().to_vec()),
+            signal::group(lookup_table.outputs().to_vec()),
+        )
+    }
+}
+
+/// the signatures of the chips that can be used as parts, looked up by name
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Library {
+    signatures: BTreeMap<String, Signature>,
+}
+
+impl Library {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// adds the signature and returns the one it replaced
+    pub fn insert(&mut self, signature: Signature) -> Option<Signature> {
+        self.signatures.insert(signature.name.clone(), signature)
+    }
+
+    pub fn add_chips<T>(&mut self, chips: &[Chip<T>]) {
+        for chip in chips {
+            self.insert(chip.into());
+        }
+    }
+
+    pub fn add_tables(&mut self, lookup_tables: &[LookupTable]) {
+        for lookup_table in lookup_tables {
+            self.insert(lookup_table.into());
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Signature> {
+        self.signatures.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.signatures.contains_key(name)
+    }
+
+    /// every signature sorted by name
+    pub fn signatures(&self) -> impl Iterator<Item = &Signature> {
+        self.signatures.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+}
+
+impl FromIterator<Signature> for Library {
+    fn from_iter<I: IntoIterator<Item = Signature>>(iter: I) -> Self {
+        let mut library = Self::new();
+        for signature in iter {
+            library.insert(signature);
+        }
+        library
+    }
+}
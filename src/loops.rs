@@ -0,0 +1,308 @@
+use crate::convert::{connection_bits, io_declarations, lookup, positional_bits};
+use crate::signal::{flatten, resolve_all};
+use crate::{
+    Chip, Component, ComponentIO, ComponentMap, Definition, Error, ErrorKind, Library, Signal, Span,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// The parts of a chip are the nodes of a graph with an edge from a part to every part
+// that reads one of its outputs. A strongly connected component of that graph is a
+// combinational loop, unless the signal enters a part through a clocked input like the
+// input of a `DFF` or the signal is allowed to close a loop.
+
+impl Chip<ComponentMap> {
+    /// a `CombinationalLoop` error for every loop of the chip, a signal in `allowed` can
+    /// close a loop like the cross-coupled outputs of a latch. The library is needed to
+    /// know which pins are outputs and which are clocked, a part with an unknown chip or
+    /// pin has no edges, see `check` for those.
+    pub fn check_loops(&self, library: &Library, allowed: &[&str]) -> Vec<Error> {
+        find_loops(self.nodes(library), allowed)
+    }
+
+    fn nodes(&self, library: &Library) -> Vec<Node> {
+        let declarations: Vec<Signal> = self.pins().cloned().collect();
+        self.parts
+            .iter()
+            .filter_map(|part| {
+                let component = Component::from_map(part, library).ok()?;
+                let signature = lookup(library, &part.name, part.span).ok()?;
+                let inputs =
+                    connection_bits(&component.inputs, signature, &declarations, part.span).ok()?;
+                let outputs =
+                    connection_bits(&component.ouputs, signature, &declarations, part.span).ok()?;
+                let (clocked, reads): (Vec<_>, Vec<_>) = inputs
+                    .into_iter()
+                    .partition(|(pin, _)| signature.is_clocked(pin.name()));
+                Some(Node {
+                    name: part.name.clone(),
+                    reads: reads.into_iter().map(|(_, signal)| signal).collect(),
+                    clocked: clocked.into_iter().map(|(_, signal)| signal).collect(),
+                    writes: outputs.into_iter().map(|(_, signal)| signal).collect(),
+                    span: part.span,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Chip<ComponentIO> {
+    /// like `Chip<ComponentMap>::check_loops`, a part with an unknown chip is
+    /// combinational and an assignment is a node too. The signals are matched to the pins
+    /// by position and width like `Chip<ComponentIO>::check_drivers`.
+    pub fn check_loops(&self, library: &Library, allowed: &[&str]) -> Vec<Error> {
+        find_loops(self.nodes(library), allowed)
+    }
+
+    fn nodes(&self, library: &Library) -> Vec<Node> {
+        let declarations = io_declarations(self, library);
+        self.parts
+            .iter()
+            .map(|part| {
+                let (reads, clocked, writes) = match library.get(&part.name) {
+                    Some(signature) if !part.is_assignment() => {
+                        let pins = flatten(signature.inputs());
+                        let (clocked, reads): (Vec<_>, Vec<_>) =
+                            positional_bits(&part.inputs, signature.inputs(), &declarations)
+                                .into_iter()
+                                .zip(pins)
+                                .partition(|(_, pin)| signature.is_clocked(pin.name()));
+                        let writes =
+                            positional_bits(&part.ouputs, signature.outputs(), &declarations);
+                        (
+                            reads.into_iter().map(|(signal, _)| signal).collect(),
+                            clocked.into_iter().map(|(signal, _)| signal).collect(),
+                            writes,
+                        )
+                    }
+                    _ => (
+                        resolve_all(&part.inputs, &declarations),
+                        Vec::new(),
+                        resolve_all(&part.ouputs, &declarations),
+                    ),
+                };
+                Node {
+                    name: if part.is_assignment() {
+                        "=".to_string()
+                    } else {
+                        part.name.clone()
+                    },
+                    reads,
+                    clocked,
+                    writes,
+                    span: part.span,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Definition {
+    /// `Chip::check_loops` of the chip, a lookup table has no parts
+    pub fn check_loops(&self, library: &Library, allowed: &[&str]) -> Vec<Error> {
+        match self {
+            Definition::Map(chip) => chip.check_loops(library, allowed),
+            Definition::IO(chip) => chip.check_loops(library, allowed),
+            Definition::Table(_) => Vec::new(),
+        }
+    }
+
+    /// the inputs that reach a clocked pin of a part but no output of the chip without
+    /// going through a clocked pin, like the `in` of a register built from `DFF`s but not
+    /// the `address` of a RAM that selects the output
+    pub(crate) fn clocked_inputs(&self, library: &Library) -> Vec<String> {
+        let (nodes, inputs, outputs) = match self {
+            Definition::Map(chip) => (chip.nodes(library), chip.inputs(), chip.outputs()),
+            Definition::IO(chip) => (chip.nodes(library), chip.inputs(), chip.outputs()),
+            Definition::Table(_) => return Vec::new(),
+        };
+        let outputs = flatten(outputs);
+        inputs
+            .iter()
+            .filter(|input| {
+                let (reached, clocked) = reach(&nodes, input.bits());
+                clocked && !outputs.iter().any(|bit| reached.contains(bit))
+            })
+            .map(|input| input.name().to_string())
+            .collect()
+    }
+}
+
+// --------------------------------- utils ---------------------------------
+
+/// a part with the bits it reads combinationally, the bits it reads through clocked pins
+/// and the bits it drives
+struct Node {
+    name: String,
+    reads: Vec<Signal>,
+    clocked: Vec<Signal>,
+    writes: Vec<Signal>,
+    span: Span,
+}
+
+/// every bit that depends combinationally on `bits` and if one of them is read by a
+/// clocked pin
+fn reach(nodes: &[Node], bits: Vec<Signal>) -> (HashSet<Signal>, bool) {
+    let mut reached: HashSet<Signal> = HashSet::new();
+    let mut queue = VecDeque::from(bits);
+    let mut clocked = false;
+    while let Some(bit) = queue.pop_front() {
+        if !reached.insert(bit.clone()) {
+            continue;
+        }
+        for node in nodes {
+            clocked |= node.clocked.contains(&bit);
+            if node.reads.contains(&bit) {
+                queue.extend(node.writes.iter().cloned());
+            }
+        }
+    }
+    (reached, clocked)
+}
+
+/// one error for every strongly connected component with the shortest loop through its
+/// first part
+fn find_loops(nodes: Vec<Node>, allowed: &[&str]) -> Vec<Error> {
+    let mut drivers: HashMap<&Signal, Vec<usize>> = HashMap::new();
+    for (i, node) in nodes.iter().enumerate() {
+        for bit in &node.writes {
+            drivers.entry(bit).or_default().push(i);
+        }
+    }
+
+    // the edges of every node to the nodes that read its outputs
+    let mut edges: Vec<Vec<(usize, &Signal)>> = vec![Vec::new(); nodes.len()];
+    for (to, node) in nodes.iter().enumerate() {
+        for bit in &node.reads {
+            if allowed.contains(&bit.name()) || bit == "true" || bit == "false" {
+                continue;
+            }
+            for &from in drivers.get(bit).into_iter().flatten() {
+                if !edges[from].iter().any(|(i, _)| *i == to) {
+                    edges[from].push((to, bit));
+                }
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    for component in strongly_connected(&edges) {
+        let start = component[0];
+        let is_loop = component.len() > 1 || edges[start].iter().any(|(to, _)| *to == start);
+        if !is_loop {
+            continue;
+        }
+
+        let path = shortest_loop(&edges, &component, start);
+        let mut names = vec![nodes[start].name.clone()];
+        for (node, bit) in path {
+            names.push(bit.to_string());
+            names.push(nodes[node].name.clone());
+        }
+        let msg = format!("combinational loop <{}>", names.join(" -> "));
+        errors.push(Error::msg_span(
+            ErrorKind::CombinationalLoop,
+            &msg,
+            nodes[start].span,
+        ));
+    }
+    errors
+}
+
+/// Tarjan's algorithm, the nodes of every component are sorted and the components are
+/// sorted by their first node
+fn strongly_connected(edges: &[Vec<(usize, &Signal)>]) -> Vec<Vec<usize>> {
+    struct State {
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(node: usize, edges: &[Vec<(usize, &Signal)>], state: &mut State) {
+        state.index[node] = Some(state.next);
+        state.low[node] = state.next;
+        state.next += 1;
+        state.stack.push(node);
+        state.on_stack[node] = true;
+
+        for &(to, _) in &edges[node] {
+            match state.index[to] {
+                None => {
+                    visit(to, edges, state);
+                    state.low[node] = state.low[node].min(state.low[to]);
+                }
+                Some(index) if state.on_stack[to] => {
+                    state.low[node] = state.low[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.low[node]) == state.index[node] {
+            let mut component = Vec::new();
+            while let Some(top) = state.stack.pop() {
+                state.on_stack[top] = false;
+                component.push(top);
+                if top == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        index: vec![None; edges.len()],
+        low: vec![0; edges.len()],
+        on_stack: vec![false; edges.len()],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for node in 0..edges.len() {
+        if state.index[node].is_none() {
+            visit(node, edges, &mut state);
+        }
+    }
+    state.components.sort_unstable();
+    state.components
+}
+
+/// the steps `(node, signal)` of the shortest path from `start` back to itself inside
+/// the component
+fn shortest_loop<'a>(
+    edges: &[Vec<(usize, &'a Signal)>],
+    component: &[usize],
+    start: usize,
+) -> Vec<(usize, &'a Signal)> {
+    let mut previous: HashMap<usize, (usize, &Signal)> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for &(to, bit) in &edges[node] {
+            if !component.contains(&to) || previous.contains_key(&to) {
+                continue;
+            }
+            previous.insert(to, (node, bit));
+            if to == start {
+                queue.clear();
+                break;
+            }
+            queue.push_back(to);
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut node = start;
+    while let Some(&(from, bit)) = previous.get(&node) {
+        path.push((node, bit));
+        node = from;
+        if node == start {
+            break;
+        }
+    }
+    path.reverse();
+    path
+}
@@ -1,211 +1,408 @@
-use crate::{Chip, ComponentMap, Error};
-use logos::{Lexer, Logos};
+use crate::{lexer, Chip, ComponentMap, Error, ErrorKind, FileId, Signal, Span, MAX_NUMBER};
+use logos::Logos;
+use std::collections::HashMap;
 use std::iter::Peekable;
 use std::slice::Iter;
 
 pub fn parse(code: &str) -> Result<Vec<Chip<ComponentMap>>, Error> {
-    let tokens = tokenize(code);
+    crate::first_error(parse_recover(code))
+}
+
+/// like `parse` but continues after an error and returns every error of the file
+pub fn parse_recover(code: &str) -> Result<Vec<Chip<ComponentMap>>, Vec<Error>> {
+    parse_file(FileId::default(), code)
+}
+
+/// like `parse_recover` but the spans of the result point into `file`
+pub fn parse_file(file: FileId, code: &str) -> Result<Vec<Chip<ComponentMap>>, Vec<Error>> {
+    let (tokens, mut errors) = lexer::tokenize(code);
     let mut t_iter = tokens.iter().peekable();
 
     let mut chips = Vec::new();
 
-    let mut name;
-    let mut inputs;
-    let mut outputs;
-    let mut parts;
+    while t_iter.peek().is_some() {
+        match get_chip(&mut t_iter, file, &mut errors) {
+            Ok(chip) => chips.push(chip),
+            Err(err) => {
+                errors.push(err);
+                sync_chip(&mut t_iter);
+            }
+        }
+    }
+    crate::recovered(file, chips, errors)
+}
 
-    loop {
-        Error::expect(t_iter.next(), TokenType::Chip)?;
-        name = get_identifier(t_iter.next())?;
+fn get_chip(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    errors: &mut Vec<Error>,
+) -> Result<Chip<ComponentMap>, Error> {
+    let first = t_iter.peek().copied();
+    Error::expect(t_iter.next(), TokenType::Chip)?;
+    let name = get_identifier(t_iter.next())?;
 
-        Error::expect(t_iter.next(), TokenType::OpenC)?;
+    Error::expect(t_iter.next(), TokenType::OpenC)?;
 
-        Error::expect(t_iter.next(), TokenType::In)?;
-        inputs = get_names(&mut t_iter)?;
-        Error::expect(t_iter.next(), TokenType::Semicolon)?;
+    let mut buses = Buses::new();
 
-        Error::expect(t_iter.next(), TokenType::Out)?;
-        outputs = get_names(&mut t_iter)?;
-        Error::expect(t_iter.next(), TokenType::Semicolon)?;
+    // like `Keyboard` without inputs or `Computer` without outputs
+    let inputs = get_declaration(t_iter, TokenType::In, &mut buses)?;
+    let outputs = get_declaration(t_iter, TokenType::Out, &mut buses)?;
 
-        Error::expect(t_iter.next(), TokenType::Parts)?;
-        Error::expect(t_iter.next(), TokenType::Colon)?;
-        parts = get_parts(&mut t_iter)?;
-        Error::expect(t_iter.next(), TokenType::CloseC)?;
+    let mut parts = Vec::new();
+    let mut builtin = None;
+    let mut clocked = Vec::new();
 
-        chips.push(Chip::new_string(name, inputs, outputs, parts));
-        if t_iter.peek().is_none() {
-            break;
+    match t_iter.next() {
+        Some(token) if token.eq_type(TokenType::Parts) => {
+            Error::expect(t_iter.next(), TokenType::Colon)?;
+            parts = get_parts(t_iter, file, &mut buses, errors);
+        }
+        Some(token) if token.eq_type(TokenType::Builtin) => {
+            builtin = Some(get_identifier(t_iter.next())?);
+            Error::expect(t_iter.next(), TokenType::Semicolon)?;
+        }
+        Some(token) => {
+            return Err(Error::msg_token(
+                &format!(
+                    "unexpected token expected <Parts> or <Builtin> but got <{:?}>",
+                    token.token_type
+                ),
+                token.clone(),
+            ))
+        }
+        None => {
+            return Err(Error::msg(
+                ErrorKind::UnexpectedEof,
+                "unexpected end of file expected token <Parts> or <Builtin>",
+            ))
         }
     }
-    Ok(chips)
+
+    if let Some(&token) = t_iter.peek() {
+        if token.eq_type(TokenType::Clocked) {
+            Error::expect(t_iter.next(), TokenType::Clocked)?;
+            clocked = get_clocked(t_iter)?;
+            Error::expect(t_iter.next(), TokenType::Semicolon)?;
+        }
+    }
+    let last = t_iter.next();
+    Error::expect(last, TokenType::CloseC)?;
+
+    Ok(Chip {
+        builtin,
+        clocked,
+        span: Span::from_tokens(file, first, last),
+        ..Chip::new_signals(&name, inputs, outputs, parts)
+    })
+}
+
+/// the width of every signal known in the current chip, an internal bus is known after
+/// it is connected to a sub bus
+type Buses = HashMap<String, usize>;
+
+/// the optional sub bus after a name, `a`, `a[3]` or `a[0..7]`
+enum Slice {
+    All,
+    Index(usize),
+    Range(usize, usize),
 }
 
 // --------------------------------- components ---------------------------------
 
-fn get_parts(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<ComponentMap>, Error> {
+/// a broken part is skipped up to its `;` so that the following parts are still checked
+fn get_parts(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    buses: &mut Buses,
+    errors: &mut Vec<Error>,
+) -> Vec<ComponentMap> {
     let mut parts = Vec::new();
 
-    parts.push(get_component(t_iter)?);
-
     while let Some(&token) = t_iter.peek() {
         if !token.eq_type(TokenType::Identifier(String::new())) {
             break;
         }
-        parts.push(get_component(t_iter)?);
+        match get_component(t_iter, file, buses) {
+            Ok(part) => parts.push(part),
+            Err(err) => {
+                errors.push(err);
+                sync_statement(t_iter);
+            }
+        }
     }
 
-    Ok(parts)
+    parts
 }
 
-fn get_component(t_iter: &mut Peekable<Iter<Token>>) -> Result<ComponentMap, Error> {
+fn get_component(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    buses: &mut Buses,
+) -> Result<ComponentMap, Error> {
+    let first = t_iter.peek().copied();
     let chip_name = get_identifier(t_iter.next())?;
     Error::expect(t_iter.next(), TokenType::OpenP)?;
 
-    let mut var_map = get_eq(t_iter)?;
+    let mut var_map = vec![get_eq(t_iter, buses)?];
 
     let mut token = t_iter.next();
     while let Some(t) = token {
         if !t.eq_type(TokenType::Comma) {
             break;
         }
-        get_eq(t_iter)?
-            .iter()
-            .for_each(|temp| var_map.push(temp.to_owned()));
+        var_map.push(get_eq(t_iter, buses)?);
         token = t_iter.next();
     }
     Error::expect(token, TokenType::CloseP)?;
-    Error::expect(t_iter.next(), TokenType::Semicolon)?;
+    let last = t_iter.next();
+    Error::expect(last, TokenType::Semicolon)?;
 
-    Ok(ComponentMap::new_string(var_map, chip_name))
+    Ok(ComponentMap {
+        span: Span::from_tokens(file, first, last),
+        ..ComponentMap::new_signals(var_map, &chip_name)
+    })
 }
 
-fn get_eq(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<(String, String)>, Error> {
-    let first = get_name(t_iter)?;
+/// `pin=signal` where both sides can be sub buses and the signal can be `true` or `false`.
+/// The widths are checked if both are known.
+fn get_eq(
+    t_iter: &mut Peekable<Iter<Token>>,
+    buses: &mut Buses,
+) -> Result<(Signal, Signal), Error> {
+    let token = t_iter.peek().copied();
+    let pin = get_signal(t_iter)?;
     Error::expect(t_iter.next(), TokenType::Equals)?;
-    let second = get_name(t_iter)?;
+    let signal = get_signal(t_iter)?;
+
+    let is_const = signal == "true" || signal == "false";
+    let signal_width = match signal.range() {
+        Some(_) => Some(signal.width()),
+        None if is_const => None,
+        None => buses.get(signal.name()).copied(),
+    };
+
+    match signal_width {
+        Some(width) if pin.is_bus() && pin.width() != width => {
+            let msg = format!(
+                "width mismatch <{}> has {} bits but <{}> has {} bits",
+                pin.name(),
+                pin.width(),
+                signal.name(),
+                width
+            );
+            return Err(Error::msg_option(&msg, token).with_kind(ErrorKind::WidthMismatch));
+        }
+        // an internal bus gets the width of the first sub bus it is connected to
+        None if pin.is_bus() && !is_const => {
+            buses.insert(signal.name().to_string(), pin.width());
+        }
+        _ => (),
+    }
+
+    Ok((pin, signal))
+}
+
+// --------------------------------- printing ---------------------------------
 
-    if first.len() != second.len() {
-        todo!();
+/// formatted source of the chips, parsing it again gives the same chips
+pub fn to_string(chips: &[Chip<ComponentMap>]) -> String {
+    chips
+        .iter()
+        .map(chip_to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn chip_to_string(chip: &Chip<ComponentMap>) -> String {
+    let mut code = format!("CHIP {} {{\n", chip.name());
+    if !chip.inputs().is_empty() {
+        code.push_str(&format!(
+            "    IN {};\n",
+            declaration_to_string(chip.inputs())
+        ));
+    }
+    if !chip.outputs().is_empty() {
+        code.push_str(&format!(
+            "    OUT {};\n",
+            declaration_to_string(chip.outputs())
+        ));
     }
 
-    let mut var_map = Vec::new();
-    for i in 0..first.len() {
-        var_map.push((first[i].to_owned(), second[i].to_owned()));
+    match chip.builtin() {
+        Some(builtin) => code.push_str(&format!("    BUILTIN {};\n", builtin)),
+        None => {
+            code.push_str("    PARTS:\n");
+            for part in chip.parts() {
+                let pins = part
+                    .pins()
+                    .map(|(pin, signal)| format!("{}={}", pin, signal))
+                    .collect::<Vec<_>>();
+                code.push_str(&format!("    {}({});\n", part.name(), pins.join(", ")));
+            }
+        }
+    }
+    if !chip.clocked().is_empty() {
+        code.push_str(&format!("    CLOCKED {};\n", chip.clocked().join(", ")));
     }
-    Ok(var_map)
+
+    code.push_str("}\n");
+    code
+}
+
+/// `a[16]` for the bits 0 to 15 and `a[2..4]` for other buses, a single bit like `a[3..3]`
+/// keeps both bounds because `a[3]` declares the bits 0 to 2
+fn declaration_to_string(signals: &[Signal]) -> String {
+    signals
+        .iter()
+        .map(|signal| match signal.range() {
+            Some((0, last)) => format!("{}[{}]", signal.name(), last + 1),
+            Some((first, last)) => format!("{}[{}..{}]", signal.name(), first, last),
+            None => signal.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// --------------------------------- recovery ---------------------------------
+
+/// skips the rest of a statement including the `;`, a `}` or the next chip is kept
+fn sync_statement(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(
+        t_iter,
+        &[TokenType::CloseC, TokenType::Chip],
+        &[TokenType::Semicolon],
+    );
+}
+
+/// skips the rest of a chip including the `}`
+fn sync_chip(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(t_iter, &[TokenType::Chip], &[TokenType::CloseC]);
 }
 
 // --------------------------------- utils ---------------------------------
 
-fn get_names(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
-    let mut names = get_name(t_iter)?;
+/// declared names, `a[16]` is a bus with 16 bits and `a[2..4]` declares the bits 2 to 4
+/// the `IN` or `OUT` section, a chip without the section has no pins
+fn get_declaration(
+    t_iter: &mut Peekable<Iter<Token>>,
+    keyword: TokenType,
+    buses: &mut Buses,
+) -> Result<Vec<Signal>, Error> {
+    match t_iter.peek() {
+        Some(&token) if token.eq_type(keyword.clone()) => {
+            Error::expect(t_iter.next(), keyword)?;
+            let names = get_names(t_iter, buses)?;
+            Error::expect(t_iter.next(), TokenType::Semicolon)?;
+            Ok(names)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn get_names(t_iter: &mut Peekable<Iter<Token>>, buses: &mut Buses) -> Result<Vec<Signal>, Error> {
+    let mut names = vec![get_name(t_iter, buses)?];
     while let Some(&token) = t_iter.peek() {
         if !token.eq_type(TokenType::Comma) {
             break;
         }
         Error::expect(t_iter.next(), TokenType::Comma)?;
-        for name in get_name(t_iter)? {
-            names.push(name);
+        names.push(get_name(t_iter, buses)?);
+    }
+
+    Ok(names)
+}
+
+fn get_name(t_iter: &mut Peekable<Iter<Token>>, buses: &mut Buses) -> Result<Signal, Error> {
+    let token = t_iter.peek().copied();
+    let (identifier, slice) = get_bus(t_iter)?;
+    let signal = match slice {
+        Slice::All => Signal::new(&identifier),
+        Slice::Index(0) => {
+            let msg = format!("bus <{}> has no bits", identifier);
+            return Err(Error::msg_option(&msg, token).with_kind(ErrorKind::InvalidRange));
+        }
+        Slice::Index(width) => Signal::bus(&identifier, width),
+        Slice::Range(start, end) => Signal::slice(&identifier, start, end),
+    };
+    buses.insert(identifier, signal.width());
+    Ok(signal)
+}
+
+/// the pins of `CLOCKED`
+fn get_clocked(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
+    let mut names = Vec::new();
+    loop {
+        names.push(get_identifier(t_iter.next())?);
+
+        match t_iter.peek() {
+            Some(&token) if token.eq_type(TokenType::Comma) => {
+                Error::expect(t_iter.next(), TokenType::Comma)?;
+            }
+            _ => break,
         }
     }
 
     Ok(names)
 }
 
-fn get_name(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
+/// a signal in a part, `a[3]` is the single bit 3
+fn get_signal(t_iter: &mut Peekable<Iter<Token>>) -> Result<Signal, Error> {
+    let (identifier, slice) = get_bus(t_iter)?;
+    Ok(match slice {
+        Slice::All => Signal::new(&identifier),
+        Slice::Index(i) => Signal::index(&identifier, i),
+        Slice::Range(start, end) => Signal::slice(&identifier, start, end),
+    })
+}
+
+fn get_bus(t_iter: &mut Peekable<Iter<Token>>) -> Result<(String, Slice), Error> {
     let identifier = get_identifier(t_iter.next())?;
     if let Some(&token) = t_iter.peek() {
         if !token.eq_type(TokenType::OpenB) {
-            return Ok(vec![identifier]);
+            return Ok((identifier, Slice::All));
         }
         Error::expect(t_iter.next(), TokenType::OpenB)?;
         let start = get_num(t_iter.next())?;
+        if let Some(&token) = t_iter.peek() {
+            if token.eq_type(TokenType::CloseB) {
+                Error::expect(t_iter.next(), TokenType::CloseB)?;
+                return Ok((identifier, Slice::Index(start)));
+            }
+        }
         Error::expect(t_iter.next(), TokenType::DoubleDot)?;
-        let end = get_num(t_iter.next())? + 1;
+        let end_token = t_iter.peek().copied();
+        let end = get_num(t_iter.next())?;
         Error::expect(t_iter.next(), TokenType::CloseB)?;
-        let mut result = Vec::new();
-        for i in start..end {
-            result.push(format!("{}{}", identifier, i));
+        if end < start {
+            let msg = format!("invalid range <{}..{}>", start, end);
+            return Err(Error::msg_option(&msg, end_token).with_kind(ErrorKind::InvalidRange));
         }
-        return Ok(result);
+        return Ok((identifier, Slice::Range(start, end)));
     }
-    Ok(vec![identifier])
+    Ok((identifier, Slice::All))
 }
 
 fn get_num(token: Option<&Token>) -> Result<usize, Error> {
-    if let TokenType::Number(num) = Error::expect(token, TokenType::Number(0))? {
-        return Ok(num);
-    } else {
-        unreachable!();
+    match Error::expect(token, TokenType::Number(0))? {
+        TokenType::Number(num) if num <= MAX_NUMBER => Ok(num),
+        _ => {
+            Err(Error::msg_option("number is too large", token)
+                .with_kind(ErrorKind::NumberTooLarge))
+        }
     }
 }
 
 fn get_identifier(token: Option<&Token>) -> Result<String, Error> {
-    let token = Error::expect(token, TokenType::Identifier(String::new()))?;
-    if let TokenType::Identifier(name) = token {
-        return Ok(name);
-    } else {
-        unreachable!();
+    match Error::expect(token, TokenType::Identifier(String::new()))? {
+        TokenType::Identifier(name) => Ok(name),
+        _ => Err(Error::msg_option("expected identifier", token)),
     }
 }
 
 // ------------------------------- tokens ------------------------------------------------
 
-fn tokenize(code: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut line = 0;
-    let mut lex = TokenType::lexer(code);
-
-    while let Some(token_type) = lex.next() {
-        if let TokenType::Ignore(ignore) = token_type {
-            if let Some(comment) = ignore {
-                if comment == "newline".to_string() {
-                    line += 1;
-                }
-            }
-        } else {
-            tokens.push(Token {
-                index: lex.span().start,
-                line,
-                len: lex.span().len(),
-                token_type,
-            });
-        }
-    }
-
-    tokens
-}
-
-#[derive(Debug, Clone, PartialEq)]
-struct Token {
-    index: usize,
-    line: usize,
-    len: usize,
-    token_type: TokenType,
-}
-
-impl crate::Token for Token {
-    type TokenType = TokenType;
-    fn line(&self) -> usize {
-        self.line
-    }
-    fn index(&self) -> usize {
-        self.index
-    }
-    fn len(&self) -> usize {
-        self.len
-    }
-    fn token_type(&self) -> Self::TokenType {
-        self.token_type.clone()
-    }
-}
-
-impl Token {
-    fn eq_type(&self, token_type: TokenType) -> bool {
-        self.token_type == token_type
-    }
-}
+type Token = lexer::Token<TokenType>;
 
 #[derive(Logos, Debug, Clone)]
 enum TokenType {
@@ -217,6 +414,10 @@ enum TokenType {
     Out,
     #[token("PARTS")]
     Parts,
+    #[token("BUILTIN")]
+    Builtin,
+    #[token("CLOCKED")]
+    Clocked,
 
     #[token("{")]
     OpenC,
@@ -242,72 +443,53 @@ enum TokenType {
     #[token(":")]
     Colon,
 
-    #[token("\t", ignore)]
-    #[token(" ", ignore)]
-    #[token("\n", ignore)]
-    Ignore(Option<String>),
+    #[token("\t", lexer::ignore)]
+    #[token(" ", lexer::ignore)]
+    #[token("\r", lexer::ignore)]
+    #[token("\n", lexer::ignore)]
+    #[regex(r"//[^\n]*", lexer::ignore)]
+    #[token("/*", lexer::block_comment)]
+    Ignore(usize),
 
     #[regex(r"[a-zA-Z_$][a-zA-Z_$0-9]+", |lex| lex.slice().parse())]
     #[regex(r"[a-zA-Z]", |lex| lex.slice().parse())]
     Identifier(String),
 
-    #[regex(r"[0-9]+", |lex| lex.slice().parse())]
+    #[regex(r"[0-9]+", lexer::number)]
     Number(usize),
 
     #[error]
     Unknown,
 }
 
+/// the value of a number, identifier or table is not compared
 impl PartialEq for TokenType {
     fn eq(&self, other: &TokenType) -> bool {
-        match (self, other) {
-            (TokenType::Number(_), TokenType::Number(_)) => true,
-            (TokenType::Identifier(_), TokenType::Identifier(_)) => true,
-            (TokenType::Ignore(_), TokenType::Ignore(_)) => true,
-
-            (TokenType::Chip, TokenType::Chip) => true,
-            (TokenType::In, TokenType::In) => true,
-            (TokenType::Out, TokenType::Out) => true,
-            (TokenType::Parts, TokenType::Parts) => true,
-
-            (TokenType::CloseB, TokenType::CloseB) => true,
-            (TokenType::CloseC, TokenType::CloseC) => true,
-            (TokenType::CloseP, TokenType::CloseP) => true,
-            (TokenType::OpenB, TokenType::OpenB) => true,
-            (TokenType::OpenC, TokenType::OpenC) => true,
-            (TokenType::OpenP, TokenType::OpenP) => true,
-
-            (TokenType::Colon, TokenType::Colon) => true,
-            (TokenType::Semicolon, TokenType::Semicolon) => true,
-            (TokenType::Comma, TokenType::Comma) => true,
-            (TokenType::Equals, TokenType::Equals) => true,
-            (TokenType::DoubleDot, TokenType::DoubleDot) => true,
-
-            (TokenType::Unknown, TokenType::Unknown) => true,
-
-            _ => false,
-        }
+        std::mem::discriminant(self) == std::mem::discriminant(other)
     }
 }
 
-fn ignore(lex: &mut Lexer<TokenType>) -> Option<Option<String>> {
-    let slice = lex.slice();
-    match slice {
-        " " => Some(None),
-        "\n" => Some(Some("newline".to_string())),
-        "\t" => Some(None),
-        _ => Some(Some(slice.to_string())),
+impl lexer::Skip for TokenType {
+    fn newlines(&self) -> Option<usize> {
+        match self {
+            TokenType::Ignore(newlines) => Some(*newlines),
+            _ => None,
+        }
+    }
+
+    fn is_unknown(&self) -> bool {
+        matches!(self, TokenType::Unknown)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{ComponentMap, Token, TokenType};
+    use super::{lexer, ComponentMap, FileId, Signal, Token, TokenType};
 
     #[test]
     fn tokneize() {
         let code = " hello, world..";
-        let tokens = super::tokenize(code);
+        let (tokens, _) = lexer::tokenize::<TokenType>(code);
         assert_eq!(
             tokens,
             vec![
@@ -339,29 +521,56 @@ mod test {
         )
     }
 
+    #[test]
+    fn comments() {
+        let code = "// header\n/* multi\nline */ a /** doc */ b";
+        let (tokens, _) = lexer::tokenize::<TokenType>(code);
+        assert_eq!(
+            tokens,
+            vec![
+                Token {
+                    index: 27,
+                    line: 2,
+                    len: 1,
+                    token_type: TokenType::Identifier("a".to_string())
+                },
+                Token {
+                    index: 40,
+                    line: 2,
+                    len: 1,
+                    token_type: TokenType::Identifier("b".to_string())
+                },
+            ]
+        )
+    }
+
     #[test]
     fn get_name() {
         let code = " hello in[2..4]";
-        let tokens = super::tokenize(code);
+        let (tokens, _) = lexer::tokenize::<TokenType>(code);
         let mut t_iter = tokens.iter().peekable();
 
-        let name = super::get_names(&mut t_iter).unwrap();
+        let mut buses = super::Buses::new();
+
+        let name = super::get_names(&mut t_iter, &mut buses).unwrap();
         assert_eq!(name, vec!["hello"]);
 
-        let name = super::get_names(&mut t_iter).unwrap();
-        assert_eq!(name, vec!["in2", "in3", "in4"]);
+        let name = super::get_names(&mut t_iter, &mut buses).unwrap();
+        assert_eq!(name, vec![Signal::slice("in", 2, 4)]);
     }
 
     #[test]
     fn get_component() {
         let code = "Nand(a=a, b=b, out=nand);";
-        let tokens = super::tokenize(code);
+        let (tokens, _) = lexer::tokenize::<TokenType>(code);
         let mut t_iter = tokens.iter().peekable();
 
-        let component = super::get_component(&mut t_iter).unwrap();
+        let component =
+            super::get_component(&mut t_iter, FileId::default(), &mut super::Buses::new()).unwrap();
         assert_eq!(
             component,
             ComponentMap::new(vec![("a", "a"), ("b", "b"), ("out", "nand")], "Nand")
         );
+        assert_eq!(component.span().range(), 0..code.len());
     }
 }
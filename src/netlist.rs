@@ -0,0 +1,583 @@
+use crate::convert::{connection_bits, io_declarations, positional};
+use crate::open_gal::OGalParse;
+use crate::shdl::ShdlParse;
+use crate::signal::{group, resolve, resolve_all};
+use crate::{Chip, ComponentIO, ComponentMap, HdlParse, Library, LookupTable, Signal, Span};
+use std::collections::HashMap;
+
+/// modules of every dialect in one shape, a module is a chip, a lookup table or an
+/// open_gal file
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Netlist {
+    modules: Vec<Module>,
+}
+
+impl Netlist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    pub fn module(&self, name: &str) -> Option<&Module> {
+        self.modules.iter().find(|module| module.name == name)
+    }
+
+    pub fn push(&mut self, module: Module) {
+        self.modules.push(module);
+    }
+
+    pub fn extend(&mut self, other: Netlist) {
+        self.modules.extend(other.modules);
+    }
+
+    /// the library gives the width of the whole pins of the parts
+    pub fn from_n2t_hdl(chips: &[Chip<ComponentMap>], library: &Library) -> Self {
+        Self {
+            modules: chips
+                .iter()
+                .map(|chip| Module::from_chip_map(chip, library))
+                .collect(),
+        }
+    }
+
+    pub fn from_comphdl(chips: &[Chip<ComponentIO>], library: &Library) -> Self {
+        Self {
+            modules: chips
+                .iter()
+                .map(|chip| Module::from_chip_io(chip, library))
+                .collect(),
+        }
+    }
+
+    pub fn from_shdl(shdl: &ShdlParse, library: &Library) -> Self {
+        let (chips, lut) = shdl;
+        Self {
+            modules: chips
+                .iter()
+                .map(|chip| Module::from_chip_io(chip, library))
+                .chain(lut.iter().map(Module::from_table))
+                .collect(),
+        }
+    }
+
+    /// an open_gal file is a single module with the name `name`
+    pub fn from_open_gal(gal: &OGalParse, name: &str) -> Self {
+        Self {
+            modules: vec![Module::from_open_gal(gal, name)],
+        }
+    }
+
+    /// the result of any parser, the module of an open_gal file is called `gal`
+    pub fn from_parse(parse: &HdlParse, library: &Library) -> Self {
+        match parse {
+            HdlParse::N2tHdl(chips) => Self::from_n2t_hdl(chips, library),
+            HdlParse::Shdl(shdl) => Self::from_shdl(shdl, library),
+            HdlParse::Comphdl(chips) => Self::from_comphdl(chips, library),
+            HdlParse::OpenGal(gal) => Self::from_open_gal(gal, "gal"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Input,
+    Output,
+}
+
+/// a single bit, the bits of a bus are called like `a[3]`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Net {
+    name: String,
+    constant: Option<bool>,
+}
+
+impl Net {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// `true` and `false` of n2t_hdl
+    pub fn constant(&self) -> Option<bool> {
+        self.constant
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Port {
+    signal: Signal,
+    direction: Direction,
+    nets: Vec<NetId>,
+}
+
+impl Port {
+    pub fn name(&self) -> &str {
+        self.signal.name()
+    }
+
+    /// the declaration like `a[16]`
+    pub fn signal(&self) -> &Signal {
+        &self.signal
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// one net for every bit
+    pub fn nets(&self) -> &[NetId] {
+        &self.nets
+    }
+
+    pub fn width(&self) -> usize {
+        self.nets.len()
+    }
+}
+
+/// n2t_hdl connects pins or sub buses like `a[0..3]` by name, shdl and comphdl connect
+/// signals by their position in the inputs or outputs
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pin {
+    Named(Signal),
+    Input(usize),
+    Output(usize),
+}
+
+/// a part that uses another module
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instance {
+    module: String,
+    connections: Vec<(Pin, Vec<NetId>)>,
+    span: Span,
+}
+
+impl Instance {
+    /// the name of the module that is used
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// every pin with the nets of its bits
+    pub fn connections(&self) -> &[(Pin, Vec<NetId>)] {
+        &self.connections
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// a truth table with one column for every output, the first input is the highest bit
+/// of the row index
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cell {
+    inputs: Vec<NetId>,
+    outputs: Vec<NetId>,
+    table: Vec<Vec<bool>>,
+    span: Span,
+}
+
+impl Cell {
+    pub fn inputs(&self) -> &[NetId] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[NetId] {
+        &self.outputs
+    }
+
+    pub fn table(&self) -> &[Vec<bool>] {
+        &self.table
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// a flip-flop that stores `d` on the clock and outputs it on `q`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Register {
+    d: NetId,
+    q: NetId,
+    span: Span,
+}
+
+impl Register {
+    pub fn d(&self) -> NetId {
+        self.d
+    }
+
+    pub fn q(&self) -> NetId {
+        self.q
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// the span is only a location and is ignored by `==`
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Module {
+    name: String,
+    ports: Vec<Port>,
+    nets: Vec<Net>,
+    instances: Vec<Instance>,
+    cells: Vec<Cell>,
+    registers: Vec<Register>,
+    builtin: Option<String>,
+    clocked: Vec<NetId>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    span: Span,
+}
+
+impl PartialEq for Module {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.ports == other.ports
+            && self.nets == other.nets
+            && self.instances == other.instances
+            && self.cells == other.cells
+            && self.registers == other.registers
+            && self.builtin == other.builtin
+            && self.clocked == other.clocked
+    }
+}
+
+impl Module {
+    /// a module with only its ports
+    pub fn new(name: &str, inputs: &[Signal], outputs: &[Signal]) -> Self {
+        let mut module = Self {
+            name: name.to_string(),
+            ..Self::default()
+        };
+        let mut nets = NetMap::new();
+        for (signals, direction) in [(inputs, Direction::Input), (outputs, Direction::Output)] {
+            for signal in signals {
+                let nets = signal
+                    .bits()
+                    .iter()
+                    .map(|bit| nets.get(&mut module, &bit.to_string()))
+                    .collect();
+                module.ports.push(Port {
+                    signal: signal.clone(),
+                    direction,
+                    nets,
+                });
+            }
+        }
+        module
+    }
+
+    /// a signal without a range is a whole pin of the chip, a constant or an internal
+    /// bus with the width of the pin of the part. A constant connects every bit of the
+    /// pin to the same net. The signals of a part with an unknown chip or pin are
+    /// connected as they are written.
+    pub fn from_chip_map(chip: &Chip<ComponentMap>, library: &Library) -> Self {
+        let mut module = Self::from_chip(chip);
+        let mut nets = NetMap::from_module(&module);
+        let declarations: Vec<Signal> = chip.pins().cloned().collect();
+
+        for part in chip.parts() {
+            let signature = library.get(part.name());
+            let mut connections = Vec::new();
+            for (pin, signal) in part.pins() {
+                let connection = [(pin.clone(), signal.clone())];
+                let bits = signature
+                    .and_then(|signature| {
+                        connection_bits(&connection, signature, &declarations, part.span()).ok()
+                    })
+                    .map(|bits| bits.into_iter().map(|(_, bit)| bit).collect())
+                    .unwrap_or_else(|| resolve(signal, &declarations));
+                let bits = bits
+                    .iter()
+                    .map(|bit| nets.get(&mut module, &bit.to_string()))
+                    .collect();
+                connections.push((Pin::Named(pin.clone()), bits));
+            }
+            module.instances.push(Instance {
+                module: part.name().to_string(),
+                connections,
+                span: part.span(),
+            });
+        }
+        module
+    }
+
+    /// an assignment becomes a buffer cell for every bit. A signal without a range is a
+    /// whole pin of the chip, a constant or an internal bus with the width of the pin of
+    /// the part, see `from_chip_map`.
+    pub fn from_chip_io(chip: &Chip<ComponentIO>, library: &Library) -> Self {
+        let mut module = Self::from_chip(chip);
+        let mut nets = NetMap::from_module(&module);
+        let declarations = io_declarations(chip, library);
+        for part in chip.parts() {
+            if part.is_assignment() {
+                let sources = resolve_all(part.inputs(), &declarations);
+                let targets = resolve_all(part.outputs(), &declarations);
+                for (source, target) in sources.iter().zip(&targets) {
+                    let cell = Cell {
+                        inputs: vec![nets.get(&mut module, &source.to_string())],
+                        outputs: vec![nets.get(&mut module, &target.to_string())],
+                        table: vec![vec![false, true]],
+                        span: part.span(),
+                    };
+                    module.cells.push(cell);
+                }
+                continue;
+            }
+
+            let signature = library.get(part.name());
+            let inputs = signal_bits(part.inputs(), signature.map(|s| s.inputs()), &declarations);
+            let outputs = signal_bits(
+                part.outputs(),
+                signature.map(|s| s.outputs()),
+                &declarations,
+            );
+            let inputs = inputs.into_iter().enumerate();
+            let outputs = outputs.into_iter().enumerate();
+            let connections = inputs
+                .map(|(i, bits)| (Pin::Input(i), bits))
+                .chain(outputs.map(|(i, bits)| (Pin::Output(i), bits)))
+                .map(|(pin, bits)| {
+                    let bits = bits
+                        .iter()
+                        .map(|bit| nets.get(&mut module, &bit.to_string()))
+                        .collect();
+                    (pin, bits)
+                })
+                .collect();
+            module.instances.push(Instance {
+                module: part.name().to_string(),
+                connections,
+                span: part.span(),
+            });
+        }
+        module
+    }
+
+    /// a module with a single cell
+    pub fn from_table(lookup_table: &LookupTable) -> Self {
+        let mut module = Self::new(
+            lookup_table.name(),
+            &group(lookup_table.inputs().to_vec()),
+            &group(lookup_table.outputs().to_vec()),
+        );
+        let cell = Cell {
+            inputs: module.port_nets(Direction::Input),
+            outputs: module.port_nets(Direction::Output),
+            table: lookup_table.table().to_vec(),
+            span: lookup_table.span(),
+        };
+        module.cells.push(cell);
+        module.span = lookup_table.span();
+        module
+    }
+
+    /// every pin that is driven by a table or a flip-flop is an output, the others are
+    /// inputs. A table output that goes through a flip-flop drives the new net `<name>.d`.
+    pub fn from_open_gal(gal: &OGalParse, name: &str) -> Self {
+        let is_output = |pin: &Signal| {
+            gal.lookup_tables()
+                .iter()
+                .any(|lookup_table| lookup_table.outputs().contains(pin))
+        };
+        let (outputs, inputs): (Vec<_>, Vec<_>) = gal
+            .pin_map()
+            .iter()
+            .map(|(pin, _)| pin.clone())
+            .partition(is_output);
+
+        let mut module = Self::new(name, &group(inputs), &group(outputs));
+        let mut nets = NetMap::from_module(&module);
+        for lookup_table in gal.lookup_tables() {
+            let inputs = lookup_table
+                .inputs()
+                .iter()
+                .map(|input| nets.get(&mut module, &input.to_string()))
+                .collect();
+            let outputs = lookup_table
+                .outputs()
+                .iter()
+                .map(|output| match gal.dff_enable().contains(output) {
+                    true => nets.get(&mut module, &format!("{}.d", output)),
+                    false => nets.get(&mut module, &output.to_string()),
+                })
+                .collect();
+            module.cells.push(Cell {
+                inputs,
+                outputs,
+                table: lookup_table.table().to_vec(),
+                span: lookup_table.span(),
+            });
+        }
+        for output in gal.dff_enable() {
+            let span = gal
+                .pins()
+                .find(|(pin, _, _)| *pin == output)
+                .map_or(Span::default(), |(_, _, span)| span);
+            let d = nets.get(&mut module, &format!("{}.d", output));
+            let q = nets.get(&mut module, &output.to_string());
+            module.registers.push(Register { d, q, span });
+        }
+        module
+    }
+
+    fn from_chip<T>(chip: &Chip<T>) -> Self {
+        let mut module = Self::new(chip.name(), chip.inputs(), chip.outputs());
+        let nets = NetMap::from_module(&module);
+        module.builtin = chip.builtin().map(str::to_string);
+        module.clocked = chip
+            .pins()
+            .filter(|pin| chip.clocked().iter().any(|name| name == pin.name()))
+            .flat_map(|pin| pin.bits())
+            .filter_map(|bit| nets.find(&bit.to_string()))
+            .collect();
+        module.span = chip.span();
+        module
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn ports(&self) -> &[Port] {
+        &self.ports
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = &Port> {
+        self.ports
+            .iter()
+            .filter(|port| port.direction == Direction::Input)
+    }
+
+    pub fn outputs(&self) -> impl Iterator<Item = &Port> {
+        self.ports
+            .iter()
+            .filter(|port| port.direction == Direction::Output)
+    }
+
+    pub fn nets(&self) -> &[Net] {
+        &self.nets
+    }
+
+    pub fn net(&self, id: NetId) -> &Net {
+        &self.nets[id.0]
+    }
+
+    pub fn find_net(&self, name: &str) -> Option<NetId> {
+        self.nets.iter().position(|net| net.name == name).map(NetId)
+    }
+
+    pub fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub fn registers(&self) -> &[Register] {
+        &self.registers
+    }
+
+    /// the name of the simulator implementation of a n2t_hdl `BUILTIN` chip
+    pub fn builtin(&self) -> Option<&str> {
+        self.builtin.as_deref()
+    }
+
+    /// the inputs of a n2t_hdl chip that only change the state on a clock edge
+    pub fn clocked(&self) -> &[NetId] {
+        &self.clocked
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    fn port_nets(&self, direction: Direction) -> Vec<NetId> {
+        self.ports
+            .iter()
+            .filter(|port| port.direction == direction)
+            .flat_map(|port| port.nets.iter().copied())
+            .collect()
+    }
+}
+
+// --------------------------------- utils ---------------------------------
+
+/// the bits of every signal of a shdl or comphdl part, without the pins of the chip the
+/// signals are read as they are written
+fn signal_bits(
+    signals: &[Signal],
+    pins: Option<&[Signal]>,
+    declarations: &[Signal],
+) -> Vec<Vec<Signal>> {
+    match pins {
+        Some(pins) => positional(signals, pins, declarations)
+            .into_iter()
+            .map(|(_, bits)| bits)
+            .collect(),
+        None => signals
+            .iter()
+            .map(|signal| resolve(signal, declarations))
+            .collect(),
+    }
+}
+
+/// finds nets by name while a module is lowered
+struct NetMap(HashMap<String, NetId>);
+
+impl NetMap {
+    fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    fn from_module(module: &Module) -> Self {
+        Self(
+            module
+                .nets
+                .iter()
+                .enumerate()
+                .map(|(i, net)| (net.name.clone(), NetId(i)))
+                .collect(),
+        )
+    }
+
+    fn find(&self, name: &str) -> Option<NetId> {
+        self.0.get(name).copied()
+    }
+
+    fn get(&mut self, module: &mut Module, name: &str) -> NetId {
+        if let Some(&id) = self.0.get(name) {
+            return id;
+        }
+        let id = NetId(module.nets.len());
+        module.nets.push(Net {
+            name: name.to_string(),
+            constant: match name {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+        });
+        self.0.insert(name.to_string(), id);
+        id
+    }
+}
@@ -1,13 +1,29 @@
-use crate::{Error, LookupTable};
-use logos::{Lexer, Logos};
+use crate::table;
+use crate::{
+    grammar, lexer, signal, Error, ErrorKind, FileId, LookupTable, OGalBuilder, Signal, Span,
+};
+use logos::Logos;
 use std::iter::Peekable;
 use std::slice::Iter;
 
-#[derive(Debug, Clone, PartialEq)]
+/// `pin_spans` has the span of the `pin` statement for every entry of `pin_map`,
+/// spans are only locations and are ignored by `==`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OGalParse {
-    pin_map: Vec<(String, usize)>,
+    pin_map: Vec<(Signal, usize)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pin_spans: Vec<Span>,
     lookup_table: Vec<LookupTable>,
-    dff_enable: Vec<String>,
+    dff_enable: Vec<Signal>,
+}
+
+impl PartialEq for OGalParse {
+    fn eq(&self, other: &Self) -> bool {
+        self.pin_map == other.pin_map
+            && self.lookup_table == other.lookup_table
+            && self.dff_enable == other.dff_enable
+    }
 }
 
 impl OGalParse {
@@ -15,6 +31,7 @@ impl OGalParse {
         Self {
             lookup_table,
             pin_map: Vec::new(),
+            pin_spans: Vec::new(),
             dff_enable: Vec::new(),
         }
     }
@@ -24,81 +41,137 @@ impl OGalParse {
         lookup_table: Vec<LookupTable>,
         dff_enable: Vec<&str>,
     ) -> Self {
-        Self {
-            pin_map: pin_map
-                .iter()
-                .map(|&(s, i)| -> (String, usize) { (s.to_string(), i) })
+        Self::new_signals(
+            pin_map
+                .into_iter()
+                .map(|(name, num)| (Signal::new(name), num))
                 .collect(),
             lookup_table,
-            dff_enable: dff_enable
-                .iter()
-                .map(|&s| -> String { s.to_string() })
-                .collect(),
-        }
+            dff_enable.into_iter().map(Signal::new).collect(),
+        )
     }
 
     pub fn new_string(
         pin_map: Vec<(String, usize)>,
         lookup_table: Vec<LookupTable>,
         dff_enable: Vec<String>,
+    ) -> Self {
+        Self::new_signals(
+            pin_map
+                .into_iter()
+                .map(|(name, num)| (Signal::new_string(name), num))
+                .collect(),
+            lookup_table,
+            dff_enable.into_iter().map(Signal::new_string).collect(),
+        )
+    }
+
+    /// like `new` with bits of a bus like `Signal::index("i", 0)` as pins
+    pub fn new_signals(
+        pin_map: Vec<(Signal, usize)>,
+        lookup_table: Vec<LookupTable>,
+        dff_enable: Vec<Signal>,
     ) -> Self {
         Self {
+            pin_spans: vec![Span::default(); pin_map.len()],
             pin_map,
             lookup_table,
             dff_enable,
         }
     }
+
+    pub fn builder() -> OGalBuilder {
+        OGalBuilder::new()
+    }
+
+    /// every pin as `(signal, number)`, `pin 1, 2 = i[0..1];` gives the bits `i[0]`
+    /// and `i[1]`
+    pub fn pin_map(&self) -> &[(Signal, usize)] {
+        &self.pin_map
+    }
+
+    /// the span of the `pin` statement of every entry in the pin map
+    pub fn pin_spans(&self) -> &[Span] {
+        &self.pin_spans
+    }
+
+    /// every pin with the span of its `pin` statement
+    pub fn pins(&self) -> impl Iterator<Item = (&Signal, usize, Span)> {
+        self.pin_map.iter().enumerate().map(|(i, (pin, num))| {
+            let span = self.pin_spans.get(i).copied().unwrap_or_default();
+            (pin, *num, span)
+        })
+    }
+
+    pub fn lookup_tables(&self) -> &[LookupTable] {
+        &self.lookup_table
+    }
+
+    /// the outputs that go through the flip-flop
+    pub fn dff_enable(&self) -> &[Signal] {
+        &self.dff_enable
+    }
 }
 
 pub fn parse(code: &str) -> Result<OGalParse, Error> {
-    let tokens = tokenize(code);
+    crate::first_error(parse_recover(code))
+}
+
+/// like `parse` but continues after an error and returns every error of the file
+pub fn parse_recover(code: &str) -> Result<OGalParse, Vec<Error>> {
+    parse_file(FileId::default(), code)
+}
+
+/// like `parse_recover` but the spans of the result point into `file`
+pub fn parse_file(file: FileId, code: &str) -> Result<OGalParse, Vec<Error>> {
+    let (tokens, mut errors) = lexer::tokenize(code);
     let mut t_iter = tokens.iter().peekable();
 
-    let mut pin_map = Vec::new();
-    let mut lookup_table = Vec::new();
-    let mut dff_enable = Vec::new();
+    let mut gal = OGalParse {
+        pin_map: Vec::new(),
+        pin_spans: Vec::new(),
+        lookup_table: Vec::new(),
+        dff_enable: Vec::new(),
+    };
+
+    while t_iter.peek().is_some() {
+        if let Err(err) = parse_statement(&mut t_iter, file, &mut gal) {
+            errors.push(err);
+            sync_statement(&mut t_iter);
+        }
+    }
+    crate::recovered(file, gal, errors)
+}
 
-    loop {
-        if let Some(&token) = t_iter.peek() {
-            if token.eq_type(TokenType::Pin) {
-                parse_pin(&mut t_iter)?
-                    .iter()
-                    .for_each(|pin| pin_map.push(pin.to_owned()));
-            } else if token.eq_type(TokenType::Table) {
-                parse_table(&mut t_iter)?
-                    .iter()
-                    .for_each(|lt| lookup_table.push(lt.to_owned()));
+fn parse_statement(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    gal: &mut OGalParse,
+) -> Result<(), Error> {
+    if let Some(&token) = t_iter.peek() {
+        if token.eq_type(TokenType::Pin) {
+            let (pins, span) = parse_pin(t_iter, file, &gal.pin_map)?;
+            gal.pin_spans.extend(vec![span; pins.len()]);
+            gal.pin_map.extend(pins);
+        } else if token.eq_type(TokenType::Table) {
+            gal.lookup_table.extend(parse_table(t_iter, file)?);
+        } else {
+            grammar::get_identifier(t_iter.peek().copied())?;
+            // `a.dff;` or `i[0..1].dff;` and every other statement is a function
+            let mut lookahead = t_iter.clone();
+            let is_dff = get_names(&mut lookahead).is_ok()
+                && matches!(lookahead.next(), Some(token) if token.eq_type(TokenType::Dot));
+            if is_dff {
+                gal.dff_enable.extend(parse_dff(t_iter)?);
             } else {
-                // no double peek (hope that compiler opimizeis clone of t_iter)
-                let mut temp = t_iter.clone();
-                get_identifier(temp.next())?;
-                if let Some(&token) = temp.peek() {
-                    if token.eq_type(TokenType::Dot) {
-                        parse_dff(&mut t_iter)?
-                            .iter()
-                            .for_each(|dff| dff_enable.push(dff.to_owned()));
-                    }
-                } else {
-                    parse_func(&mut t_iter)?
-                        .iter()
-                        .for_each(|lt| lookup_table.push(lt.to_owned()));
-                }
+                gal.lookup_table.extend(parse_func(t_iter, file)?);
             }
         }
-
-        if t_iter.peek().is_none() {
-            break;
-        }
     }
-
-    Ok(OGalParse {
-        pin_map,
-        lookup_table,
-        dff_enable,
-    })
+    Ok(())
 }
 
-fn parse_dff(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
+fn parse_dff(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<Signal>, Error> {
     let inputs = get_names(t_iter)?;
     Error::expect(t_iter.next(), TokenType::Dot)?;
     Error::expect(t_iter.next(), TokenType::Dff)?;
@@ -107,11 +180,41 @@ fn parse_dff(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
     Ok(inputs)
 }
 
-fn parse_func(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<LookupTable>, Error> {
-    Ok(Vec::new())
+/// `a = (!b | (c));` the inputs are the variables in the order they first appear, a
+/// variable can also be a bit like `i[0]`
+fn parse_func(t_iter: &mut Peekable<Iter<Token>>, file: FileId) -> Result<Vec<LookupTable>, Error> {
+    let first = t_iter.peek().copied();
+    let output = grammar::get_identifier(t_iter.next())?;
+    let output = grammar::get_variable(t_iter, &output)?;
+    Error::expect(t_iter.next(), TokenType::Equals)?;
+    let mut inputs: Vec<Signal> = Vec::new();
+    // a new variable is a new input
+    let mut var = |input: Signal, _: &Token| {
+        Ok(match inputs.iter().position(|bit| *bit == input) {
+            Some(i) => i,
+            None => {
+                inputs.push(input);
+                inputs.len() - 1
+            }
+        })
+    };
+    let expr = grammar::get_expr(t_iter, &mut var, 0)?;
+    let last = t_iter.next();
+    Error::expect(last, TokenType::Semicolon)?;
+
+    table::check_size(inputs.len(), 1)?;
+    let table = expr.truth_table(inputs.len());
+    Ok(vec![LookupTable {
+        span: Span::from_tokens(file, first, last),
+        ..LookupTable::new_signals("", inputs, vec![output], vec![table])
+    }])
 }
 
-fn parse_table(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<LookupTable>, Error> {
+fn parse_table(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+) -> Result<Vec<LookupTable>, Error> {
+    let first = t_iter.peek().copied();
     Error::expect(t_iter.next(), TokenType::Table)?;
     Error::expect(t_iter.next(), TokenType::OpenP)?;
     let in_names = get_names(t_iter)?;
@@ -126,26 +229,160 @@ fn parse_table(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<LookupTable>, E
     if let Some(&token) = t_iter.peek() {
         if token.eq_type(TokenType::Dot) {
             Error::expect(t_iter.next(), TokenType::Dot)?;
+            match t_iter.next() {
+                Some(token) if token.eq_type(TokenType::Count) => is_count = true,
+                Some(token) if token.eq_type(TokenType::Fill) => {
+                    is_fill = true;
+                    Error::expect(t_iter.next(), TokenType::OpenP)?;
+                    fill = grammar::get_bit(t_iter.next())?;
+                    Error::expect(t_iter.next(), TokenType::CloseP)?;
+                }
+                Some(token) => {
+                    return Err(Error::msg_token(
+                        "expected <count> or <fill>",
+                        token.clone(),
+                    ))
+                }
+                None => {
+                    return Err(Error::msg(
+                        ErrorKind::UnexpectedEof,
+                        "unexpected end of file expected <count> or <fill>",
+                    ))
+                }
+            }
         }
     }
 
-    if is_count {
+    Error::expect(t_iter.next(), TokenType::OpenC)?;
+    let lookup_table = if is_count {
+        let (token, values) = grammar::get_count(t_iter)?;
+        table::count(String::new(), in_names, out_names, token, values)?
     } else if is_fill {
+        let rows = grammar::get_rows(t_iter, in_names.len())?;
+        table::fill(String::new(), in_names, out_names, fill, rows)?
     } else {
-    }
-
-    Ok(Vec::new())
+        let rows = grammar::get_rows(t_iter, in_names.len())?;
+        table::full(String::new(), in_names, out_names, rows)?
+    };
+    let last = t_iter.next();
+    Error::expect(last, TokenType::CloseC)?;
+
+    Ok(vec![LookupTable {
+        span: Span::from_tokens(file, first, last),
+        ..lookup_table
+    }])
 }
 
-fn parse_pin(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<(String, usize)>, Error> {
+/// `pin 1, 2 = i[0..1];` or `pin [13..16] = and, or, xor, not;`
+/// every pin number and name can only be used once in `pin_map` and the new pins
+fn parse_pin(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    pin_map: &[(Signal, usize)],
+) -> Result<(Vec<(Signal, usize)>, Span), Error> {
+    let start = t_iter.peek().map(|&token| token.clone());
     Error::expect(t_iter.next(), TokenType::Pin)?;
+    let nums = get_nums(t_iter)?;
+    Error::expect(t_iter.next(), TokenType::Equals)?;
     let names = get_names(t_iter)?;
-    Ok(Vec::new())
+
+    // the error covers the whole statement
+    let error = |msg: &str, end: Option<&&Token>| match (&start, end) {
+        (Some(start), Some(&end)) => Error::msg_len(msg, start.clone(), end.index - start.index),
+        (Some(start), None) => Error::msg_token(msg, start.clone()),
+        (None, _) => Error::msg(ErrorKind::UnexpectedEof, msg),
+    };
+
+    if nums.len() != names.len() {
+        let msg = format!(
+            "expected the same number of pins and names but got {} pins and {} names",
+            nums.len(),
+            names.len()
+        );
+        return Err(error(&msg, t_iter.peek()).with_kind(ErrorKind::WidthMismatch));
+    }
+
+    let mut pins: Vec<(Signal, usize)> = Vec::new();
+    for (name, num) in names.into_iter().zip(nums) {
+        let used = pin_map
+            .iter()
+            .chain(&pins)
+            .find(|pin| pin.0 == name || pin.1 == num);
+        if let Some((old_name, old_num)) = used {
+            let msg = format!(
+                "pin <{}> = <{}> conflicts with pin <{}> = <{}>",
+                num, name, old_num, old_name
+            );
+            return Err(error(&msg, t_iter.peek()).with_kind(ErrorKind::DuplicatePin));
+        }
+        pins.push((name, num));
+    }
+    let last = t_iter.next();
+    Error::expect(last, TokenType::Semicolon)?;
+
+    Ok((pins, Span::from_tokens(file, start.as_ref(), last)))
+}
+
+// --------------------------------- printing ---------------------------------
+
+/// formatted source with the pins, every lookup table as a full `table` and the
+/// flip-flops, parsing it again gives the same result
+pub fn to_string(gal: &OGalParse) -> String {
+    let pins = gal
+        .pin_map
+        .iter()
+        .map(|(pin, num)| format!("pin {} = {};\n", num, pin))
+        .collect::<String>();
+    let tables = gal.lookup_table.iter().map(|lookup_table| {
+        let rows = table::to_rows(lookup_table)
+            .iter()
+            .map(|row| format!("    {}\n", row))
+            .collect::<String>();
+        format!(
+            "table({} -> {}) {{\n{}}}\n",
+            names_to_string(lookup_table.inputs()),
+            names_to_string(lookup_table.outputs()),
+            rows
+        )
+    });
+    let dffs = gal
+        .dff_enable
+        .iter()
+        .map(|pin| format!("{}.dff;\n", pin))
+        .collect::<String>();
+
+    std::iter::once(pins)
+        .chain(tables)
+        .chain(std::iter::once(dffs))
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// the bits of a bus are merged into a range like `i[0..1]`
+fn names_to_string(bits: &[Signal]) -> String {
+    signal::group(bits.to_vec())
+        .iter()
+        .map(Signal::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// --------------------------------- recovery ---------------------------------
+
+/// skips the rest of a statement including the `;` or the `}` of a table,
+/// the next `pin` or `table` is kept
+fn sync_statement(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(
+        t_iter,
+        &[TokenType::Pin, TokenType::Table],
+        &[TokenType::Semicolon, TokenType::CloseC],
+    );
 }
 
 // --------------------------------- utils ---------------------------------
 
-fn get_names(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
+fn get_names(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<Signal>, Error> {
     let mut names = get_name(t_iter)?;
     while let Some(&token) = t_iter.peek() {
         if !token.eq_type(TokenType::Comma) {
@@ -160,117 +397,64 @@ fn get_names(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
     Ok(names)
 }
 
-fn get_name(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<String>, Error> {
-    let identifier = get_identifier(t_iter.next())?;
-    if let Some(&token) = t_iter.peek() {
-        if !token.eq_type(TokenType::OpenB) {
-            return Ok(vec![identifier]);
-        }
-        Error::expect(t_iter.next(), TokenType::OpenB)?;
-        let start = get_num(t_iter.next())?;
-        Error::expect(t_iter.next(), TokenType::DoubleDot)?;
-        let end = get_num(t_iter.next())? + 1;
-        Error::expect(t_iter.next(), TokenType::CloseB)?;
-        let mut result = Vec::new();
-        for i in start..end {
-            result.push(format!("{}{}", identifier, i));
+/// `a`, the bit `a[3]` or every bit of `a[2..5]`
+fn get_name(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<Signal>, Error> {
+    let identifier = grammar::get_identifier(t_iter.next())?;
+    match t_iter.peek() {
+        Some(&token) if token.eq_type(TokenType::OpenB) => {
+            let (start, end) = grammar::get_range(t_iter)?;
+            Ok(Signal::slice(&identifier, start, end).bits())
         }
-        return Ok(result);
+        _ => Ok(vec![Signal::new_string(identifier)]),
     }
-    Ok(vec![identifier])
 }
 
+/// comma separated numbers and ranges like `1, 2, [5..8]`
 fn get_nums(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<usize>, Error> {
-    Ok(Vec::new())
-}
-
-fn get_num(token: Option<&Token>) -> Result<usize, Error> {
-    if let TokenType::Number(num) = Error::expect(token, TokenType::Number(0))? {
-        return Ok(num);
-    } else {
-        unreachable!();
-    }
-}
-
-fn get_identifier(token: Option<&Token>) -> Result<String, Error> {
-    let token = Error::expect(token, TokenType::Identifier(String::new()))?;
-    if let TokenType::Identifier(name) = token {
-        return Ok(name);
-    } else {
-        unreachable!();
-    }
-}
-
-// ------------------------------- tokens ------------------------------------------------
-
-fn tokenize(code: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut line = 0;
-    let mut lex = TokenType::lexer(code);
-
-    while let Some(token_type) = lex.next() {
-        if let TokenType::Ignore(ignore) = token_type {
-            if let Some(comment) = ignore {
-                if comment == "newline".to_string() {
-                    line += 1;
-                }
+    let mut nums = Vec::new();
+    loop {
+        if let Some(&token) = t_iter.peek() {
+            if token.eq_type(TokenType::OpenB) {
+                let (start, end) = grammar::get_range(t_iter)?;
+                nums.extend(start..=end);
+            } else {
+                nums.push(grammar::get_num(t_iter.next())?);
             }
         } else {
-            tokens.push(Token {
-                index: lex.span().start,
-                line,
-                len: lex.span().len(),
-                token_type,
-            });
+            nums.push(grammar::get_num(t_iter.next())?);
         }
-    }
 
-    tokens
-}
+        match t_iter.peek() {
+            Some(&token) if token.eq_type(TokenType::Comma) => {
+                Error::expect(t_iter.next(), TokenType::Comma)?;
+            }
+            _ => break,
+        }
+    }
 
-#[derive(Debug, Clone, PartialEq)]
-struct Token {
-    index: usize,
-    line: usize,
-    len: usize,
-    token_type: TokenType,
+    Ok(nums)
 }
 
-impl crate::Token for Token {
-    type TokenType = TokenType;
-    fn line(&self) -> usize {
-        self.line
-    }
-    fn index(&self) -> usize {
-        self.index
-    }
-    fn len(&self) -> usize {
-        self.len
-    }
-    fn token_type(&self) -> Self::TokenType {
-        self.token_type.clone()
-    }
-}
+// ------------------------------- tokens ------------------------------------------------
 
-impl Token {
-    fn eq_type(&self, token_type: TokenType) -> bool {
-        self.token_type == token_type
-    }
-}
+type Token = lexer::Token<TokenType>;
 
 #[derive(Logos, Debug, Clone)]
 pub enum TokenType {
-    #[regex(r"[0-9]+", |lex| lex.slice().parse())]
+    #[regex(r"[0-9]+", lexer::number)]
     Number(usize),
-    #[regex(r"[0|1]+", table, priority = 3)]
+    #[regex(r"[01]+", lexer::table, priority = 3)]
     BoolTable(Vec<bool>),
     #[regex(r"[a-zA-Z_$][a-zA-Z_$0-9]+", |lex| lex.slice().parse())]
     #[regex(r"[a-zA-Z]", |lex| lex.slice().parse())]
     Identifier(String),
-    #[token("\t", ignore)]
-    #[token(" ", ignore)]
-    #[token("\n", ignore)]
-    Ignore(Option<String>),
+    #[token("\t", lexer::ignore)]
+    #[token(" ", lexer::ignore)]
+    #[token("\r", lexer::ignore)]
+    #[token("\n", lexer::ignore)]
+    #[regex(r"//[^\n]*", lexer::ignore)]
+    #[token("/*", lexer::block_comment)]
+    Ignore(usize),
 
     #[token("pin")]
     Pin,
@@ -322,60 +506,58 @@ pub enum TokenType {
     Unknown,
 }
 
-fn ignore(lex: &mut Lexer<TokenType>) -> Option<Option<String>> {
-    let slice = lex.slice();
-    match slice {
-        " " => Some(None),
-        "\n" => Some(Some("newline".to_string())),
-        "\t" => Some(None),
-        _ => Some(Some(slice.to_string())),
+/// the value of a number, identifier or table is not compared
+impl PartialEq for TokenType {
+    fn eq(&self, other: &TokenType) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
     }
 }
 
-fn table(lex: &mut Lexer<TokenType>) -> Option<Vec<bool>> {
-    let slice = lex.slice();
-    let mut result = Vec::new();
-    for c in slice.chars() {
-        result.push(c == '1');
+impl lexer::Skip for TokenType {
+    fn newlines(&self) -> Option<usize> {
+        match self {
+            TokenType::Ignore(newlines) => Some(*newlines),
+            _ => None,
+        }
+    }
+
+    fn is_unknown(&self) -> bool {
+        matches!(self, TokenType::Unknown)
     }
-    Some(result)
 }
 
-impl PartialEq for TokenType {
-    fn eq(&self, other: &TokenType) -> bool {
-        match (self, other) {
-            (TokenType::Number(_), TokenType::Number(_)) => true,
-            (TokenType::Identifier(_), TokenType::Identifier(_)) => true,
-            (TokenType::Ignore(_), TokenType::Ignore(_)) => true,
-
-            (TokenType::Pin, TokenType::Pin) => true,
-            (TokenType::Table, TokenType::Table) => true,
-            (TokenType::Count, TokenType::Count) => true,
-            (TokenType::Fill, TokenType::Fill) => true,
-
-            (TokenType::CloseB, TokenType::CloseB) => true,
-            (TokenType::CloseC, TokenType::CloseC) => true,
-            (TokenType::CloseP, TokenType::CloseP) => true,
-            (TokenType::OpenB, TokenType::OpenB) => true,
-            (TokenType::OpenC, TokenType::OpenC) => true,
-            (TokenType::OpenP, TokenType::OpenP) => true,
-
-            (TokenType::Not, TokenType::Not) => true,
-            (TokenType::Or, TokenType::Or) => true,
-            (TokenType::And, TokenType::And) => true,
-            (TokenType::Xor, TokenType::Xor) => true,
-
-            (TokenType::Dff, TokenType::Dff) => true,
-            (TokenType::Semicolon, TokenType::Semicolon) => true,
-            (TokenType::Comma, TokenType::Comma) => true,
-            (TokenType::Equals, TokenType::Equals) => true,
-            (TokenType::DoubleDot, TokenType::DoubleDot) => true,
-            (TokenType::Arrow, TokenType::Arrow) => true,
-            (TokenType::Dot, TokenType::Dot) => true,
-
-            (TokenType::Unknown, TokenType::Unknown) => true,
-
-            _ => false,
+impl grammar::Grammar for TokenType {
+    const IDENTIFIER: Self = TokenType::Identifier(String::new());
+    const NUMBER: Self = TokenType::Number(0);
+    const BOOL_TABLE: Self = TokenType::BoolTable(Vec::new());
+    const AND: Self = TokenType::And;
+    const OR: Self = TokenType::Or;
+    const XOR: Self = TokenType::Xor;
+    const NOT: Self = TokenType::Not;
+    const OPEN_P: Self = TokenType::OpenP;
+    const CLOSE_P: Self = TokenType::CloseP;
+    const OPEN_B: Self = TokenType::OpenB;
+    const CLOSE_B: Self = TokenType::CloseB;
+    const DOUBLE_DOT: Self = TokenType::DoubleDot;
+
+    fn identifier(&self) -> Option<&str> {
+        match self {
+            TokenType::Identifier(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn number(&self) -> Option<usize> {
+        match self {
+            TokenType::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    fn bools(&self) -> Option<&[bool]> {
+        match self {
+            TokenType::BoolTable(bools) => Some(bools),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,277 @@
+use crate::{
+    detect, Chip, ComponentIO, ComponentMap, Error, ErrorKind, FileId, HdlParse, Library,
+    LookupTable, Signature, SourceMap, Span,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::{fs, io};
+
+/// the file extensions that `load_dir` reads
+const EXTENSIONS: [&str; 3] = ["hdl", "shdl", "comphdl"];
+
+/// a chip of a project in any of the dialects
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Definition {
+    /// a chip of n2t_hdl
+    Map(Chip<ComponentMap>),
+    /// a chip of shdl or comphdl
+    IO(Chip<ComponentIO>),
+    /// a truth table or functions of shdl
+    Table(LookupTable),
+}
+
+impl Definition {
+    pub fn name(&self) -> &str {
+        match self {
+            Definition::Map(chip) => chip.name(),
+            Definition::IO(chip) => chip.name(),
+            Definition::Table(lookup_table) => lookup_table.name(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Definition::Map(chip) => chip.span(),
+            Definition::IO(chip) => chip.span(),
+            Definition::Table(lookup_table) => lookup_table.span(),
+        }
+    }
+
+    pub fn file(&self) -> FileId {
+        self.span().file
+    }
+
+    pub fn signature(&self) -> Signature {
+        match self {
+            Definition::Map(chip) => chip.into(),
+            Definition::IO(chip) => chip.into(),
+            Definition::Table(lookup_table) => lookup_table.into(),
+        }
+    }
+
+    /// the names of the chips that are used as parts with the span of the part,
+    /// assignments are not parts
+    pub fn parts(&self) -> Vec<(&str, Span)> {
+        match self {
+            Definition::Map(chip) => chip
+                .parts()
+                .iter()
+                .map(|part| (part.name(), part.span()))
+                .collect(),
+            Definition::IO(chip) => chip
+                .parts()
+                .iter()
+                .filter(|part| !part.is_assignment())
+                .map(|part| (part.name(), part.span()))
+                .collect(),
+            Definition::Table(_) => Vec::new(),
+        }
+    }
+}
+
+/// the chips of several files indexed by name. Like in nand2tetris a part is resolved
+/// by the name of its chip, first in the project and then in the builtin chips.
+#[derive(Debug, Clone, Default)]
+pub struct Project {
+    sources: SourceMap,
+    chips: BTreeMap<String, Definition>,
+    errors: Vec<Error>,
+}
+
+impl Project {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// every `.hdl`, `.shdl` and `.comphdl` file of the directory sorted by name
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let extension = path.extension().and_then(|extension| extension.to_str());
+            if path.is_file() && extension.is_some_and(|e| EXTENSIONS.contains(&e)) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Self::load_files(paths)
+    }
+
+    /// the files in the given order, the dialect of every file is detected
+    pub fn load_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> io::Result<Self> {
+        let mut project = Self::new();
+        for path in paths {
+            let path = path.as_ref();
+            let code = fs::read_to_string(path)?;
+            project.add(&path.display().to_string(), &code);
+        }
+        Ok(project)
+    }
+
+    /// parses `code` and adds its chips, the errors of the file are kept in `errors`
+    pub fn add(&mut self, name: &str, code: &str) -> FileId {
+        let file = self.sources.add(name, code);
+        let parse = detect(code)
+            .map_err(|err| vec![err.with_file(file)])
+            .and_then(|dialect| dialect.parse_file(file, code));
+
+        let definitions = match parse {
+            Ok(HdlParse::N2tHdl(chips)) => chips.into_iter().map(Definition::Map).collect(),
+            Ok(HdlParse::Comphdl(chips)) => chips.into_iter().map(Definition::IO).collect(),
+            Ok(HdlParse::Shdl((chips, lut))) => {
+                let chips = chips.into_iter().map(Definition::IO);
+                chips
+                    .chain(lut.into_iter().map(Definition::Table))
+                    .collect()
+            }
+            Ok(HdlParse::OpenGal(_)) => {
+                let msg = format!("<{}> is open_gal which has no chips", name);
+                self.errors
+                    .push(Error::msg(ErrorKind::Unsupported, &msg).with_file(file));
+                Vec::new()
+            }
+            Err(errors) => {
+                self.errors.extend(errors);
+                Vec::new()
+            }
+        };
+
+        for definition in definitions {
+            if let Some(first) = self.chips.get(definition.name()) {
+                let msg = format!(
+                    "chip <{}> is already defined in <{}>",
+                    definition.name(),
+                    self.sources.name(first.file()).unwrap_or_default()
+                );
+                self.errors.push(Error::msg_span(
+                    ErrorKind::DuplicateChip,
+                    &msg,
+                    definition.span(),
+                ));
+            } else {
+                self.chips.insert(definition.name().to_string(), definition);
+            }
+        }
+        file
+    }
+
+    /// the names and sources of every file, `SourceMap::render` shows the errors
+    pub fn sources(&self) -> &SourceMap {
+        &self.sources
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Definition> {
+        self.chips.get(name)
+    }
+
+    /// every chip sorted by name, a duplicate chip is only the first definition
+    pub fn definitions(&self) -> impl Iterator<Item = &Definition> {
+        self.chips.values()
+    }
+
+    /// the parse errors and duplicate chips of the added files
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// the signatures of every chip of the project
+    pub fn library(&self) -> Library {
+        self.chips.values().map(Definition::signature).collect()
+    }
+
+    /// the signatures of the project and the `builtins` it uses, a chip of the project
+    /// replaces a builtin chip with the same name. A chip that uses a sequential part is
+    /// clocked, see `Signature::is_sequential`. Fails with the errors of `errors`, parts
+    /// that use an unknown chip and chips that depend on themselves.
+    pub fn resolve(&self, builtins: &Library) -> Result<Library, Vec<Error>> {
+        let mut errors = self.errors.clone();
+        let mut library = self.library();
+        for definition in self.chips.values() {
+            for (name, span) in definition.parts() {
+                if self.chips.contains_key(name) {
+                    continue;
+                }
+                match builtins.get(name) {
+                    Some(signature) => {
+                        library.insert(signature.clone());
+                    }
+                    None => errors.push(Error::msg_span(
+                        ErrorKind::UnknownChip,
+                        &format!("unknown chip <{}>", name),
+                        span,
+                    )),
+                }
+            }
+        }
+        errors.extend(self.cycles());
+
+        if errors.is_empty() {
+            self.clock(&mut library);
+            return Ok(library);
+        }
+        errors.sort_by_key(|err| (err.file, err.index.unwrap_or(usize::MAX)));
+        Err(errors)
+    }
+
+    /// a chip of the project without `CLOCKED` is clocked on the inputs that only reach
+    /// clocked pins of its parts, like the `in` of a `Register` that is built from `Bit`s,
+    /// see `Definition::clocked_inputs`. A chip gets more clocked inputs when its parts
+    /// do, so this is repeated until nothing changes.
+    fn clock(&self, library: &mut Library) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for definition in self.chips.values() {
+                let signature = definition.signature();
+                if signature.is_sequential() {
+                    continue;
+                }
+                let clocked = definition.clocked_inputs(library);
+                let known = library.get(definition.name()).map(Signature::clocked);
+                if clocked.is_empty() || known == Some(clocked.as_slice()) {
+                    continue;
+                }
+                let clocked: Vec<&str> = clocked.iter().map(String::as_str).collect();
+                library.insert(signature.with_clocked(&clocked));
+                changed = true;
+            }
+        }
+    }
+
+    /// an error for every cycle at the part that closes it
+    fn cycles(&self) -> Vec<Error> {
+        let mut errors = Vec::new();
+        let mut done = HashSet::new();
+        for name in self.chips.keys() {
+            self.visit(name, &mut Vec::new(), &mut done, &mut errors);
+        }
+        errors
+    }
+
+    fn visit<'a>(
+        &'a self,
+        name: &'a str,
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        errors: &mut Vec<Error>,
+    ) {
+        let definition = match self.chips.get(name) {
+            Some(definition) if !done.contains(name) => definition,
+            _ => return,
+        };
+        path.push(name);
+        for (part, span) in definition.parts() {
+            if let Some(start) = path.iter().position(|chip| *chip == part) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(part);
+                let msg = format!("circular dependency <{}>", cycle.join(" -> "));
+                errors.push(Error::msg_span(ErrorKind::CircularDependency, &msg, span));
+            } else {
+                self.visit(part, path, done, errors);
+            }
+        }
+        path.pop();
+        done.insert(name);
+    }
+}
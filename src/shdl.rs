@@ -1,7 +1,532 @@
-use crate::{Chip, ComponentIO, Error, LookupTable};
+use crate::expr::Expr;
+use crate::table;
+use crate::{
+    grammar, lexer, signal, Chip, ComponentIO, Error, ErrorKind, FileId, LookupTable, Signal, Span,
+};
+use logos::Logos;
+use std::iter::Peekable;
+use std::slice::Iter;
+
+/// chips with parts and chips that are defined by a truth table or functions
+pub type ShdlParse = (Vec<Chip<ComponentIO>>, Vec<LookupTable>);
+
+pub fn parse(code: &str) -> Result<ShdlParse, Error> {
+    crate::first_error(parse_recover(code))
+}
+
+/// like `parse` but continues after an error and returns every error of the file
+pub fn parse_recover(code: &str) -> Result<ShdlParse, Vec<Error>> {
+    parse_file(FileId::default(), code)
+}
+
+/// like `parse_recover` but the spans of the result point into `file`
+pub fn parse_file(file: FileId, code: &str) -> Result<ShdlParse, Vec<Error>> {
+    let (tokens, mut errors) = lexer::tokenize(code);
+    let mut t_iter = tokens.iter().peekable();
 
-pub fn parse(code: &str) -> Result<(Vec<Chip<ComponentIO>>, Vec<LookupTable>), Error> {
     let mut chips = Vec::new();
     let mut lut = Vec::new();
-    Ok((chips, lut))
+
+    while t_iter.peek().is_some() {
+        if let Err(err) = get_chip(&mut t_iter, file, &mut chips, &mut lut, &mut errors) {
+            errors.push(err);
+            sync_chip(&mut t_iter);
+        }
+    }
+    crate::recovered(file, (chips, lut), errors)
+}
+
+/// a chip with a `parts` section is added to `chips` and every other one to `lut`
+fn get_chip(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    chips: &mut Vec<Chip<ComponentIO>>,
+    lut: &mut Vec<LookupTable>,
+    errors: &mut Vec<Error>,
+) -> Result<(), Error> {
+    let first = t_iter.peek().copied();
+    Error::expect(t_iter.next(), TokenType::Chip)?;
+    let name = grammar::get_identifier(t_iter.next())?;
+
+    Error::expect(t_iter.next(), TokenType::OpenC)?;
+
+    expect_keyword(t_iter.next(), "in")?;
+    Error::expect(t_iter.next(), TokenType::Colon)?;
+    let inputs = get_names(t_iter)?;
+    Error::expect(t_iter.next(), TokenType::Semicolon)?;
+
+    expect_keyword(t_iter.next(), "out")?;
+    Error::expect(t_iter.next(), TokenType::Colon)?;
+    let outputs = get_names(t_iter)?;
+    Error::expect(t_iter.next(), TokenType::Semicolon)?;
+
+    // the section names are no keywords so they can still be used as signal names
+    let section = match t_iter.next() {
+        Some(token) => token,
+        None => {
+            return Err(Error::msg(
+                ErrorKind::UnexpectedEof,
+                "unexpected end of file expected chip body",
+            ))
+        }
+    };
+    let kind = grammar::get_identifier(Some(section))?;
+    Error::expect(t_iter.next(), TokenType::Colon)?;
+
+    let mut chip = None;
+    let mut lookup_table = None;
+    if kind == "parts" {
+        let parts = get_parts(t_iter, file, errors);
+        chip = Some(Chip::new_signals(
+            &name,
+            inputs.clone(),
+            outputs.clone(),
+            parts,
+        ));
+    }
+    // a table has a column for every bit
+    let inputs = signal::flatten(&inputs);
+    let outputs = signal::flatten(&outputs);
+    match kind.as_str() {
+        "parts" => (),
+        "full" => {
+            let rows = grammar::get_rows(t_iter, inputs.len())?;
+            lookup_table = Some(table::full(name, inputs, outputs, rows)?);
+        }
+        "fill0" | "fill1" => {
+            let rows = grammar::get_rows(t_iter, inputs.len())?;
+            lookup_table = Some(table::fill(name, inputs, outputs, kind == "fill1", rows)?);
+        }
+        "count" => {
+            let (token, values) = grammar::get_count(t_iter)?;
+            lookup_table = Some(table::count(name, inputs, outputs, token, values)?);
+        }
+        "func" => {
+            lookup_table = get_funcs(t_iter, name, inputs, outputs, errors)?;
+        }
+        _ => {
+            return Err(Error::msg_token(
+                "expected one of <parts>, <full>, <fill0>, <fill1>, <count> or <func>",
+                section.clone(),
+            ))
+        }
+    }
+
+    let last = t_iter.next();
+    Error::expect(last, TokenType::CloseC)?;
+
+    let span = Span::from_tokens(file, first, last);
+    if let Some(chip) = chip {
+        chips.push(Chip { span, ..chip });
+    }
+    if let Some(lookup_table) = lookup_table {
+        lut.push(LookupTable {
+            span,
+            ..lookup_table
+        });
+    }
+    Ok(())
+}
+
+// --------------------------------- components ---------------------------------
+
+/// a broken part is skipped up to its `;` so that the following parts are still checked
+fn get_parts(
+    t_iter: &mut Peekable<Iter<Token>>,
+    file: FileId,
+    errors: &mut Vec<Error>,
+) -> Vec<ComponentIO> {
+    let mut parts = Vec::new();
+
+    while let Some(&token) = t_iter.peek() {
+        if !token.eq_type(TokenType::Identifier(String::new())) {
+            break;
+        }
+        match get_component(t_iter, file) {
+            Ok(part) => parts.push(part),
+            Err(err) => {
+                errors.push(err);
+                sync_statement(t_iter);
+            }
+        }
+    }
+
+    parts
+}
+
+/// `q, q_n = RS_FF(r, s);` or the plain assignment `out[0..3] = x0, x[1..3];`
+fn get_component(t_iter: &mut Peekable<Iter<Token>>, file: FileId) -> Result<ComponentIO, Error> {
+    let first = t_iter.peek().copied();
+    let outputs = get_names(t_iter)?;
+    Error::expect(t_iter.next(), TokenType::Equals)?;
+
+    let is_component =
+        matches!(lexer::peek_second(t_iter), Some(token) if token.eq_type(TokenType::OpenP));
+
+    if !is_component {
+        let inputs = get_names(t_iter)?;
+        let last = t_iter.next();
+        Error::expect(last, TokenType::Semicolon)?;
+        return Ok(ComponentIO {
+            span: Span::from_tokens(file, first, last),
+            ..ComponentIO::new_signals(inputs, outputs, "")
+        });
+    }
+
+    let chip_name = grammar::get_identifier(t_iter.next())?;
+    Error::expect(t_iter.next(), TokenType::OpenP)?;
+    let mut inputs = Vec::new();
+    if let Some(&token) = t_iter.peek() {
+        if !token.eq_type(TokenType::CloseP) {
+            inputs = get_names(t_iter)?;
+        }
+    }
+    Error::expect(t_iter.next(), TokenType::CloseP)?;
+    let last = t_iter.next();
+    Error::expect(last, TokenType::Semicolon)?;
+
+    Ok(ComponentIO {
+        span: Span::from_tokens(file, first, last),
+        ..ComponentIO::new_signals(inputs, outputs, &chip_name)
+    })
+}
+
+// --------------------------------- functions ---------------------------------
+
+/// `None` if a function could not be parsed, the error is already in `errors`
+fn get_funcs(
+    t_iter: &mut Peekable<Iter<Token>>,
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    errors: &mut Vec<Error>,
+) -> Result<Option<LookupTable>, Error> {
+    table::check_size(inputs.len(), outputs.len())?;
+    let mut table = vec![None; outputs.len()];
+    let mut failed = false;
+
+    while let Some(&token) = t_iter.peek() {
+        if !token.eq_type(TokenType::Identifier(String::new())) {
+            break;
+        }
+        let (output, expr) = match get_func(t_iter, &inputs) {
+            Ok(func) => func,
+            Err(err) => {
+                errors.push(err);
+                sync_statement(t_iter);
+                failed = true;
+                continue;
+            }
+        };
+
+        match outputs.iter().position(|bit| *bit == output) {
+            Some(i) if table[i].is_some() => errors.push(
+                Error::msg_token(
+                    &format!("output <{}> is already defined", output),
+                    token.clone(),
+                )
+                .with_kind(ErrorKind::DuplicateOutput),
+            ),
+            Some(i) => table[i] = Some(expr.truth_table(inputs.len())),
+            None => errors.push(
+                Error::msg_token(&format!("unknown output <{}>", output), token.clone())
+                    .with_kind(ErrorKind::UnknownSignal),
+            ),
+        }
+    }
+
+    if failed {
+        return Ok(None);
+    }
+
+    let mut result = Vec::new();
+    for (output, column) in outputs.iter().zip(table) {
+        match column {
+            Some(column) => result.push(column),
+            None => {
+                return Err(Error::msg(
+                    ErrorKind::MissingOutput,
+                    &format!("output <{}> of <{}> has no function", output, name),
+                ))
+            }
+        }
+    }
+
+    Ok(Some(LookupTable::new_signals(
+        &name, inputs, outputs, result,
+    )))
+}
+
+/// `out = a & !b;` or `out[1] = a[0] & a[1];`
+fn get_func(
+    t_iter: &mut Peekable<Iter<Token>>,
+    inputs: &[Signal],
+) -> Result<(Signal, Expr), Error> {
+    let output = get_name(t_iter)?;
+    Error::expect(t_iter.next(), TokenType::Equals)?;
+    let mut var = |input: Signal, token: &Token| {
+        inputs.iter().position(|bit| *bit == input).ok_or_else(|| {
+            Error::msg_token(&format!("unknown input <{}>", input), token.clone())
+                .with_kind(ErrorKind::UnknownSignal)
+        })
+    };
+    let expr = grammar::get_expr(t_iter, &mut var, 0)?;
+    Error::expect(t_iter.next(), TokenType::Semicolon)?;
+
+    Ok((output, expr))
+}
+
+// --------------------------------- printing ---------------------------------
+
+/// formatted source of the chips followed by the lookup tables as `full` tables,
+/// parsing it again gives the same result
+pub fn to_string(shdl: &ShdlParse) -> String {
+    let (chips, lut) = shdl;
+    chips
+        .iter()
+        .map(chip_to_string)
+        .chain(lut.iter().map(table_to_string))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn chip_to_string(chip: &Chip<ComponentIO>) -> String {
+    let mut code = header_to_string(
+        chip.name(),
+        &names_to_string(chip.inputs()),
+        &names_to_string(chip.outputs()),
+    );
+    code.push_str("    parts:\n");
+    for part in chip.parts() {
+        let outputs = names_to_string(part.outputs());
+        let inputs = names_to_string(part.inputs());
+        if part.is_assignment() {
+            code.push_str(&format!("        {} = {};\n", outputs, inputs));
+        } else {
+            code.push_str(&format!(
+                "        {} = {}({});\n",
+                outputs,
+                part.name(),
+                inputs
+            ));
+        }
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn table_to_string(lookup_table: &LookupTable) -> String {
+    let mut code = header_to_string(
+        lookup_table.name(),
+        &names_to_string(&signal::group(lookup_table.inputs().to_vec())),
+        &names_to_string(&signal::group(lookup_table.outputs().to_vec())),
+    );
+    code.push_str("    full:\n");
+    for row in table::to_rows(lookup_table) {
+        code.push_str(&format!("        {}\n", row));
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn header_to_string(name: &str, inputs: &str, outputs: &str) -> String {
+    format!(
+        "chip {} {{\n    in: {};\n    out: {};\n",
+        name, inputs, outputs
+    )
+}
+
+/// shdl has no descending ranges so their bits are written one by one
+fn names_to_string(names: &[Signal]) -> String {
+    names
+        .iter()
+        .flat_map(|name| match name.range() {
+            Some((first, last)) if first > last => name.bits(),
+            _ => vec![name.clone()],
+        })
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// --------------------------------- recovery ---------------------------------
+
+/// skips the rest of a statement including the `;`, a `}` or the next chip is kept
+fn sync_statement(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(
+        t_iter,
+        &[TokenType::CloseC, TokenType::Chip],
+        &[TokenType::Semicolon],
+    );
+}
+
+/// skips the rest of a chip including the `}`
+fn sync_chip(t_iter: &mut Peekable<Iter<Token>>) {
+    lexer::skip(t_iter, &[TokenType::Chip], &[TokenType::CloseC]);
+}
+
+// --------------------------------- utils ---------------------------------
+
+fn get_names(t_iter: &mut Peekable<Iter<Token>>) -> Result<Vec<Signal>, Error> {
+    let mut names = vec![get_name(t_iter)?];
+    while let Some(&token) = t_iter.peek() {
+        if !token.eq_type(TokenType::Comma) {
+            break;
+        }
+        Error::expect(t_iter.next(), TokenType::Comma)?;
+        names.push(get_name(t_iter)?);
+    }
+
+    Ok(names)
+}
+
+/// `a`, the bit `a[3]` or the bits `a[2..5]`
+fn get_name(t_iter: &mut Peekable<Iter<Token>>) -> Result<Signal, Error> {
+    let identifier = grammar::get_identifier(t_iter.next())?;
+    match t_iter.peek() {
+        Some(&token) if token.eq_type(TokenType::OpenB) => {
+            let (start, end) = grammar::get_range(t_iter)?;
+            Ok(Signal::slice(&identifier, start, end))
+        }
+        _ => Ok(Signal::new(&identifier)),
+    }
+}
+
+fn expect_keyword(token: Option<&Token>, keyword: &str) -> Result<(), Error> {
+    match token {
+        Some(Token {
+            token_type: TokenType::Identifier(name),
+            ..
+        }) if name == keyword => Ok(()),
+        Some(token) => Err(Error::msg_token(
+            &format!(
+                "unexpected token expected <{}> but got <{:?}>",
+                keyword, token.token_type
+            ),
+            token.clone(),
+        )),
+        None => Err(Error::msg(
+            ErrorKind::UnexpectedEof,
+            &format!("unexpected end of file expected <{}>", keyword),
+        )),
+    }
+}
+
+// ------------------------------- tokens ------------------------------------------------
+
+type Token = lexer::Token<TokenType>;
+
+#[derive(Logos, Debug, Clone)]
+enum TokenType {
+    #[token("chip")]
+    Chip,
+
+    #[token("{")]
+    OpenC,
+    #[token("}")]
+    CloseC,
+    #[token("(")]
+    OpenP,
+    #[token(")")]
+    CloseP,
+    #[token("[")]
+    OpenB,
+    #[token("]")]
+    CloseB,
+
+    #[token(",")]
+    Comma,
+    #[token(";")]
+    Semicolon,
+    #[token("=")]
+    Equals,
+    #[token("..")]
+    DoubleDot,
+    #[token(":")]
+    Colon,
+
+    #[token("&")]
+    And,
+    #[token("|")]
+    Or,
+    #[token("^")]
+    Xor,
+    #[token("!")]
+    Not,
+
+    #[token("\t", lexer::ignore)]
+    #[token(" ", lexer::ignore)]
+    #[token("\r", lexer::ignore)]
+    #[token("\n", lexer::ignore)]
+    #[regex(r"//[^\n]*", lexer::ignore)]
+    #[token("/*", lexer::block_comment)]
+    Ignore(usize),
+
+    #[regex(r"[a-zA-Z_$][a-zA-Z_$0-9]+", |lex| lex.slice().parse())]
+    #[regex(r"[a-zA-Z]", |lex| lex.slice().parse())]
+    Identifier(String),
+
+    #[regex(r"[0-9]+", lexer::number)]
+    Number(usize),
+    #[regex(r"[01]+", lexer::table, priority = 3)]
+    BoolTable(Vec<bool>),
+
+    #[error]
+    Unknown,
+}
+
+/// the value of a number, identifier or table is not compared
+impl PartialEq for TokenType {
+    fn eq(&self, other: &TokenType) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl lexer::Skip for TokenType {
+    fn newlines(&self) -> Option<usize> {
+        match self {
+            TokenType::Ignore(newlines) => Some(*newlines),
+            _ => None,
+        }
+    }
+
+    fn is_unknown(&self) -> bool {
+        matches!(self, TokenType::Unknown)
+    }
+}
+
+impl grammar::Grammar for TokenType {
+    const IDENTIFIER: Self = TokenType::Identifier(String::new());
+    const NUMBER: Self = TokenType::Number(0);
+    const BOOL_TABLE: Self = TokenType::BoolTable(Vec::new());
+    const AND: Self = TokenType::And;
+    const OR: Self = TokenType::Or;
+    const XOR: Self = TokenType::Xor;
+    const NOT: Self = TokenType::Not;
+    const OPEN_P: Self = TokenType::OpenP;
+    const CLOSE_P: Self = TokenType::CloseP;
+    const OPEN_B: Self = TokenType::OpenB;
+    const CLOSE_B: Self = TokenType::CloseB;
+    const DOUBLE_DOT: Self = TokenType::DoubleDot;
+
+    fn identifier(&self) -> Option<&str> {
+        match self {
+            TokenType::Identifier(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn number(&self) -> Option<usize> {
+        match self {
+            TokenType::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    fn bools(&self) -> Option<&[bool]> {
+        match self {
+            TokenType::BoolTable(bools) => Some(bools),
+            _ => None,
+        }
+    }
 }
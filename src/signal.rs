@@ -0,0 +1,227 @@
+use std::fmt::{self, Display};
+
+/// a signal or a bus like `a`, `a[3]` or `a[0..7]`. The range is inclusive and in the
+/// written order, comphdl allows `a[7:0]` where the first bit is 7. Without a range the
+/// signal is a single bit in a declaration and the whole signal in a part.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SignalRepr"))]
+pub struct Signal {
+    name: String,
+    range: Option<(usize, usize)>,
+}
+
+impl Signal {
+    pub fn new(name: &str) -> Self {
+        Self::new_string(name.to_string())
+    }
+
+    pub fn new_string(name: String) -> Self {
+        Self { name, range: None }
+    }
+
+    /// the bits `first` to `last`, `last` can be smaller than `first`
+    pub fn slice(name: &str, first: usize, last: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            range: Some((first, last)),
+        }
+    }
+
+    /// the bits `0` to `width - 1` like the n2t_hdl declaration `a[16]`, a bus has at
+    /// least one bit
+    pub fn bus(name: &str, width: usize) -> Self {
+        Self::slice(name, 0, width.max(1) - 1)
+    }
+
+    pub fn index(name: &str, index: usize) -> Self {
+        Self::slice(name, index, index)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn range(&self) -> Option<(usize, usize)> {
+        self.range
+    }
+
+    pub fn is_bus(&self) -> bool {
+        self.range.is_some()
+    }
+
+    /// number of bits, a signal without a range has one
+    pub fn width(&self) -> usize {
+        match self.range {
+            Some((first, last)) => first.max(last) - first.min(last) + 1,
+            None => 1,
+        }
+    }
+
+    /// every bit in the written order, a signal without a range is its own bit
+    pub fn bits(&self) -> Vec<Signal> {
+        let (first, last) = match self.range {
+            Some(range) => range,
+            None => return vec![self.clone()],
+        };
+        let bits: Vec<_> = if first <= last {
+            (first..=last).collect()
+        } else {
+            (last..=first).rev().collect()
+        };
+        bits.into_iter()
+            .map(|i| Self::index(&self.name, i))
+            .collect()
+    }
+}
+
+/// `a`, `a[3]` or `a[0..7]`
+impl Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.range {
+            None => write!(f, "{}", self.name),
+            Some((first, last)) if first == last => write!(f, "{}[{}]", self.name, first),
+            Some((first, last)) => write!(f, "{}[{}..{}]", self.name, first, last),
+        }
+    }
+}
+
+impl From<&str> for Signal {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+/// a plain name without a range
+impl PartialEq<str> for Signal {
+    fn eq(&self, other: &str) -> bool {
+        self.range.is_none() && self.name == other
+    }
+}
+
+impl PartialEq<&str> for Signal {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Signal {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+/// a plain name like `"a"` is read as a signal without a range
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum SignalRepr {
+    Name(String),
+    Signal {
+        name: String,
+        range: Option<(usize, usize)>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl From<SignalRepr> for Signal {
+    fn from(repr: SignalRepr) -> Self {
+        match repr {
+            SignalRepr::Name(name) => Self::new_string(name),
+            SignalRepr::Signal { name, range } => Self { name, range },
+        }
+    }
+}
+
+// --------------------------------- utils ---------------------------------
+
+/// every bit of the signals in order
+pub(crate) fn flatten(signals: &[Signal]) -> Vec<Signal> {
+    signals.iter().flat_map(Signal::bits).collect()
+}
+
+/// the bits of a signal of a part, a name without a range is the whole declared signal
+/// or a single bit if it is not declared
+pub(crate) fn resolve(signal: &Signal, declarations: &[Signal]) -> Vec<Signal> {
+    match declarations.iter().find(|d| d.name == signal.name) {
+        Some(declaration) if !signal.is_bus() => declaration.bits(),
+        _ => signal.bits(),
+    }
+}
+
+/// every bit of the signals in order, see `resolve`
+pub(crate) fn resolve_all(signals: &[Signal], declarations: &[Signal]) -> Vec<Signal> {
+    signals
+        .iter()
+        .flat_map(|signal| resolve(signal, declarations))
+        .collect()
+}
+
+/// merges connections of single bits `(pin, signal)` that follow each other into
+/// ascending slices, like `a[0]=x[4], a[1]=x[5]` to `a[0..1]=x[4..5]`
+pub(crate) fn join(bits: Vec<(Signal, Signal)>) -> Vec<(Signal, Signal)> {
+    let mut joined: Vec<(Signal, Signal)> = Vec::new();
+    for (pin, signal) in bits {
+        if let Some((last_pin, last_signal)) = joined.last_mut() {
+            if let Some((next_pin, next_signal)) =
+                extend(last_pin, &pin).zip(extend(last_signal, &signal))
+            {
+                if same_direction(last_pin, &next_pin, last_signal, &next_signal) {
+                    *last_pin = next_pin;
+                    *last_signal = next_signal;
+                    continue;
+                }
+            }
+        }
+        joined.push((pin, signal));
+    }
+
+    joined
+        .into_iter()
+        .map(|(pin, signal)| match (pin.range, signal.range) {
+            (Some((a, b)), Some((c, d))) if a > b => (
+                Signal::slice(&pin.name, b, a),
+                Signal::slice(&signal.name, d, c),
+            ),
+            _ => (pin, signal),
+        })
+        .collect()
+}
+
+/// merges bits that follow each other into slices and keeps the order, like `a[3], a[2]`
+/// to `a[3..2]`
+pub(crate) fn group(bits: Vec<Signal>) -> Vec<Signal> {
+    let mut grouped: Vec<Signal> = Vec::new();
+    for bit in bits {
+        if let Some(last) = grouped.last_mut() {
+            if let Some(next) = extend(last, &bit) {
+                *last = next;
+                continue;
+            }
+        }
+        grouped.push(bit);
+    }
+    grouped
+}
+
+/// `signal` extended by `bit` if the bit follows directly after its last bit
+fn extend(signal: &Signal, bit: &Signal) -> Option<Signal> {
+    let (first, last) = signal.range?;
+    let (index, _) = bit.range?;
+    if signal.name != bit.name || bit.width() != 1 {
+        return None;
+    }
+    let follows = match first.cmp(&last) {
+        std::cmp::Ordering::Less => index == last + 1,
+        std::cmp::Ordering::Greater => last > 0 && index == last - 1,
+        std::cmp::Ordering::Equal => index == last + 1 || (last > 0 && index == last - 1),
+    };
+    follows.then(|| Signal::slice(&signal.name, first, index))
+}
+
+fn same_direction(pin: &Signal, next_pin: &Signal, signal: &Signal, next_signal: &Signal) -> bool {
+    let up = |old: &Signal, new: &Signal| {
+        new.range.map(|(_, last)| last) > old.range.map(|(_, last)| last)
+    };
+    up(pin, next_pin) == up(signal, next_signal)
+}
@@ -0,0 +1,76 @@
+use crate::{Error, Position, Token};
+use std::ops::Range;
+
+/// index of a file in a `SourceMap`, parsing without a source map uses the default id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileId(usize);
+
+/// byte range `start..end` in the source of `file`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub file: FileId,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(file: FileId, start: usize, end: usize) -> Self {
+        Self { file, start, end }
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// from the start of `first` to the end of `last`, both are always known after a
+    /// successful parse
+    pub(crate) fn from_tokens<T: Token>(file: FileId, first: Option<&T>, last: Option<&T>) -> Self {
+        let start = first.map_or(0, |token| token.index());
+        let end = last.map_or(start, |token| token.index() + token.len());
+        Self::new(file, start, end.max(start))
+    }
+}
+
+/// the names and sources of every parsed file, turns spans into lines and columns
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<(String, String)>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, source: &str) -> FileId {
+        self.files.push((name.to_string(), source.to_string()));
+        FileId(self.files.len() - 1)
+    }
+
+    pub fn name(&self, file: FileId) -> Option<&str> {
+        self.files.get(file.0).map(|(name, _)| name.as_str())
+    }
+
+    pub fn source(&self, file: FileId) -> Option<&str> {
+        self.files.get(file.0).map(|(_, source)| source.as_str())
+    }
+
+    /// the source text of the span
+    pub fn snippet(&self, span: Span) -> Option<&str> {
+        self.source(span.file)?.get(span.range())
+    }
+
+    /// 1-based line and column of the start of the span, the column counts characters
+    pub fn location(&self, span: Span) -> Option<(usize, usize)> {
+        let pos = Position::new(self.source(span.file)?, Some(span.range()));
+        Some((pos.line, pos.column))
+    }
+
+    /// `Error::render` with the name and source of the file of the error
+    pub fn render(&self, err: &Error) -> Option<String> {
+        let file = err.file();
+        Some(err.render(self.name(file)?, self.source(file)?))
+    }
+}
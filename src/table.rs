@@ -0,0 +1,190 @@
+use crate::{Error, ErrorKind, LookupTable, Signal, Token};
+
+/// a table has `2^inputs` rows so the size has to be limited
+const MAX_INPUTS: usize = 16;
+const MAX_VALUES: usize = 1 << 20;
+
+/// one row of a truth table with the token it was read from (used for errors)
+pub(crate) struct Row<T: Token> {
+    pub token: T,
+    pub inputs: Vec<bool>,
+    pub outputs: Vec<bool>,
+}
+
+/// every combination of the inputs has to be listed exactly once
+pub(crate) fn full<T: Token>(
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    rows: Vec<Row<T>>,
+) -> Result<LookupTable, Error> {
+    check_size(inputs.len(), outputs.len())?;
+    let table = fill_rows(inputs.len(), outputs.len(), &rows)?;
+
+    let mut result = Vec::new();
+    for column in table {
+        let mut values = Vec::new();
+        for (i, value) in column.iter().enumerate() {
+            if let Some(value) = value {
+                values.push(*value);
+            } else {
+                return Err(Error::msg(
+                    ErrorKind::InvalidTable,
+                    &format!(
+                        "table <{}> is missing the row {}",
+                        name,
+                        to_bits(i, inputs.len())
+                    ),
+                ));
+            }
+        }
+        result.push(values);
+    }
+
+    Ok(LookupTable::new_signals(&name, inputs, outputs, result))
+}
+
+/// only the rows that differ from `fill` are listed
+pub(crate) fn fill<T: Token>(
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    fill: bool,
+    rows: Vec<Row<T>>,
+) -> Result<LookupTable, Error> {
+    check_size(inputs.len(), outputs.len())?;
+    let table = fill_rows(inputs.len(), outputs.len(), &rows)?
+        .iter()
+        .map(|column| column.iter().map(|value| value.unwrap_or(fill)).collect())
+        .collect();
+
+    Ok(LookupTable::new_signals(&name, inputs, outputs, table))
+}
+
+/// the output values are listed in the order of the counting inputs (00, 01, 10, 11, ...)
+pub(crate) fn count<T: Token>(
+    name: String,
+    inputs: Vec<Signal>,
+    outputs: Vec<Signal>,
+    token: T,
+    values: Vec<bool>,
+) -> Result<LookupTable, Error> {
+    check_size(inputs.len(), outputs.len())?;
+    let rows = 1 << inputs.len();
+    if values.len() != rows * outputs.len() {
+        return Err(Error::msg_token(
+            &format!(
+                "expected {} values ({} rows with {} outputs) but got {}",
+                rows * outputs.len(),
+                rows,
+                outputs.len(),
+                values.len()
+            ),
+            token,
+        )
+        .with_kind(ErrorKind::InvalidTable));
+    }
+
+    let mut table = vec![Vec::new(); outputs.len()];
+    for (i, &value) in values.iter().enumerate() {
+        table[i % outputs.len()].push(value);
+    }
+
+    Ok(LookupTable::new_signals(&name, inputs, outputs, table))
+}
+
+pub(crate) fn check_size(num_in: usize, num_out: usize) -> Result<(), Error> {
+    if num_in > MAX_INPUTS || (1usize << num_in).saturating_mul(num_out) > MAX_VALUES {
+        return Err(Error::msg(
+            ErrorKind::TableTooLarge,
+            &format!(
+                "truth table with {} inputs and {} outputs is too large",
+                num_in, num_out
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn fill_rows<T: Token>(
+    num_in: usize,
+    num_out: usize,
+    rows: &[Row<T>],
+) -> Result<Vec<Vec<Option<bool>>>, Error> {
+    let mut table = vec![vec![None; 1 << num_in]; num_out];
+
+    for row in rows {
+        if row.inputs.len() != num_in {
+            return Err(Error::msg_token(
+                &format!(
+                    "expected {} input values but got {}",
+                    num_in,
+                    row.inputs.len()
+                ),
+                row.token.clone(),
+            )
+            .with_kind(ErrorKind::InvalidTable));
+        }
+        if row.outputs.len() != num_out {
+            return Err(Error::msg_token(
+                &format!(
+                    "expected {} output values but got {}",
+                    num_out,
+                    row.outputs.len()
+                ),
+                row.token.clone(),
+            )
+            .with_kind(ErrorKind::InvalidTable));
+        }
+
+        let index = row
+            .inputs
+            .iter()
+            .fold(0, |index, &bit| (index << 1) | bit as usize);
+
+        for (column, &value) in table.iter_mut().zip(&row.outputs) {
+            let msg = match column[index] {
+                Some(old) if old != value => "conflicting row",
+                Some(_) => "duplicate row",
+                None => {
+                    column[index] = Some(value);
+                    continue;
+                }
+            };
+            return Err(Error::msg_token(msg, row.token.clone()).with_kind(ErrorKind::InvalidTable));
+        }
+    }
+
+    Ok(table)
+}
+
+/// every row of the table like `01 1` with the inputs and outputs separated by a space,
+/// a table without inputs only has its outputs. A column of a table that was not checked
+/// by a parser can be too short, its missing values are printed as `0`.
+pub(crate) fn to_rows(lookup_table: &LookupTable) -> Vec<String> {
+    let num_in = lookup_table.inputs().len();
+    (0..1usize << num_in)
+        .map(|row| {
+            let outputs = lookup_table
+                .table()
+                .iter()
+                .map(|column| match column.get(row) {
+                    Some(true) => '1',
+                    _ => '0',
+                })
+                .collect::<String>();
+            if num_in == 0 {
+                outputs
+            } else {
+                format!("{} {}", to_bits(row, num_in), outputs)
+            }
+        })
+        .collect()
+}
+
+fn to_bits(index: usize, len: usize) -> String {
+    (0..len)
+        .rev()
+        .map(|i| if index >> i & 1 == 1 { '1' } else { '0' })
+        .collect()
+}
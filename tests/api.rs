@@ -0,0 +1,250 @@
+use hdl_parser::{n2t_hdl, open_gal, shdl, Chip, ComponentMap, ErrorKind, LookupTable};
+
+#[test]
+fn chip_getters() {
+    let code = r"
+    CHIP And {
+        IN a, b;
+        OUT out;
+        PARTS:
+        Nand(a=a, b=b, out=n);
+        Not(in=n, out=out);
+    }
+    CHIP Bit {
+        IN in, load;
+        OUT out;
+        BUILTIN Bit;
+        CLOCKED in, load;
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+
+    assert_eq!(chips[0].name(), "And");
+    assert_eq!(chips[0].inputs(), ["a", "b"]);
+    assert_eq!(chips[0].outputs(), ["out"]);
+    assert_eq!(chips[0].pins().collect::<Vec<_>>(), ["a", "b", "out"]);
+    assert_eq!(chips[0].builtin(), None);
+    assert_eq!(
+        chips[0]
+            .parts()
+            .iter()
+            .map(|part| part.name())
+            .collect::<Vec<_>>(),
+        ["Nand", "Not"]
+    );
+    assert_eq!(
+        chips[0].parts()[1]
+            .pins()
+            .map(|(pin, signal)| (pin.to_string(), signal.to_string()))
+            .collect::<Vec<_>>(),
+        [
+            ("in".to_string(), "n".to_string()),
+            ("out".to_string(), "out".to_string())
+        ]
+    );
+
+    assert_eq!(chips[1].builtin(), Some("Bit"));
+    assert_eq!(chips[1].clocked(), ["in", "load"]);
+    assert!(chips[1].parts().is_empty());
+}
+
+#[test]
+fn shdl_getters() {
+    let code = r"
+    chip Half {
+        in: a, b;
+        out: s, c;
+        func:
+            s = a ^ b;
+            c = a & b;
+    }
+    chip And {
+        in: a, b;
+        out: x;
+        parts:
+            n = Nand(a, b);
+            x = n;
+    }";
+    let (chips, tables) = shdl::parse(code).unwrap();
+
+    assert_eq!(tables[0].name(), "Half");
+    assert_eq!(tables[0].inputs(), ["a", "b"]);
+    assert_eq!(tables[0].outputs(), ["s", "c"]);
+    assert_eq!(tables[0].table()[0], [false, true, true, false]);
+    assert_eq!(tables[0].eval(&[true, true]), Some(vec![false, true]));
+    assert_eq!(tables[0].eval(&[true]), None);
+
+    let parts = chips[0].parts();
+    assert_eq!(parts[0].name(), "Nand");
+    assert_eq!(parts[0].inputs(), ["a", "b"]);
+    assert_eq!(parts[0].outputs(), ["n"]);
+    assert!(!parts[0].is_assignment());
+    assert!(parts[1].is_assignment());
+}
+
+#[test]
+fn open_gal_getters() {
+    let code = r"
+    pin 1, 2 = a, b;
+    pin 23 = x;
+    x = a & b;
+    x.dff;";
+    let gal = open_gal::parse(code).unwrap();
+
+    assert_eq!(
+        gal.pins()
+            .map(|(pin, num, _)| (pin.to_string(), num))
+            .collect::<Vec<_>>(),
+        [
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("x".to_string(), 23)
+        ]
+    );
+    let (_, _, span) = gal.pins().nth(2).unwrap();
+    assert_eq!(&code[span.range()], "pin 23 = x;");
+    assert_eq!(gal.lookup_tables()[0].outputs(), ["x"]);
+    assert_eq!(gal.dff_enable(), ["x"]);
+}
+
+#[test]
+fn chip_builder() {
+    let chip = Chip::builder("And")
+        .inputs(&["a", "b"])
+        .output("out")
+        .part(ComponentMap::new(
+            vec![("a", "a"), ("b", "b"), ("out", "n")],
+            "Nand",
+        ))
+        .part(ComponentMap::new(vec![("in", "n"), ("out", "out")], "Not"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        chip,
+        Chip::new(
+            "And",
+            vec!["a", "b"],
+            vec!["out"],
+            vec![
+                ComponentMap::new(vec![("a", "a"), ("b", "b"), ("out", "n")], "Nand"),
+                ComponentMap::new(vec![("in", "n"), ("out", "out")], "Not"),
+            ]
+        )
+    );
+
+    let bit = Chip::<ComponentMap>::builder("Bit")
+        .inputs(&["in", "load"])
+        .output("out")
+        .builtin("Bit")
+        .clocked(&["in", "load"])
+        .build()
+        .unwrap();
+    assert_eq!(
+        bit,
+        Chip::new_builtin(
+            "Bit",
+            vec!["in", "load"],
+            vec!["out"],
+            "Bit",
+            vec!["in", "load"]
+        )
+    );
+}
+
+#[test]
+fn chip_builder_errors() {
+    let kind = |builder: hdl_parser::ChipBuilder<ComponentMap>| {
+        builder.build().unwrap_err().kind().clone()
+    };
+
+    assert_eq!(kind(Chip::builder("1And")), ErrorKind::InvalidName);
+    assert_eq!(
+        kind(Chip::builder("And").input("a b")),
+        ErrorKind::InvalidName
+    );
+    assert_eq!(
+        kind(Chip::builder("And").inputs(&["a", "b"]).output("a")),
+        ErrorKind::DuplicatePin
+    );
+    assert_eq!(
+        kind(Chip::builder("Bit").input("in").clocked(&["load"])),
+        ErrorKind::UnknownSignal
+    );
+    assert_eq!(
+        kind(
+            Chip::builder("Bit")
+                .builtin("Bit")
+                .part(ComponentMap::new(vec![], "Nand"))
+        ),
+        ErrorKind::BuiltinWithParts
+    );
+}
+
+#[test]
+fn lookup_table_builder() {
+    let table = LookupTable::builder("And")
+        .inputs(&["a", "b"])
+        .output("x")
+        .table(vec![vec![false, false, false, true]])
+        .build()
+        .unwrap();
+    assert_eq!(
+        table,
+        LookupTable::new(
+            "And",
+            vec!["a", "b"],
+            vec!["x"],
+            vec![vec![false, false, false, true]]
+        )
+    );
+
+    let err = LookupTable::builder("And")
+        .inputs(&["a", "b"])
+        .output("x")
+        .table(vec![vec![false, true]])
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidTable);
+
+    let inputs = (0..32).map(|i| format!("i{}", i)).collect::<Vec<_>>();
+    let err = LookupTable::builder("Big")
+        .inputs(&inputs.iter().map(String::as_str).collect::<Vec<_>>())
+        .output("x")
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::TableTooLarge);
+}
+
+#[test]
+fn open_gal_builder() {
+    let table = LookupTable::builder("")
+        .inputs(&["a", "b"])
+        .output("x")
+        .table(vec![vec![false, false, false, true]])
+        .build()
+        .unwrap();
+    let gal = open_gal::OGalParse::builder()
+        .pin("a", 1)
+        .pin("b", 2)
+        .pin("x", 23)
+        .lookup_table(table)
+        .dff("x")
+        .build()
+        .unwrap();
+    assert_eq!(
+        gal,
+        open_gal::parse("pin 1, 2, 23 = a, b, x; x = a & b; x.dff;").unwrap()
+    );
+
+    let err = open_gal::OGalParse::builder()
+        .pin("a", 1)
+        .pin("b", 1)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::DuplicatePin);
+    let err = open_gal::OGalParse::builder()
+        .pin("a", 1)
+        .dff("x")
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnknownSignal);
+}
@@ -0,0 +1,90 @@
+use hdl_parser::builtin::{self, Model};
+use hdl_parser::{n2t_hdl, Project, Signal};
+
+#[test]
+fn library() {
+    let library = builtin::library();
+    assert_eq!(library.len(), 30);
+
+    let ram = library.get("RAM16K").unwrap();
+    assert_eq!(ram.pin("address"), Some(&Signal::bus("address", 14)));
+    assert!(ram.is_clocked("load"));
+    assert!(!ram.is_clocked("address"));
+    assert!(library.get("PC").unwrap().is_sequential());
+    assert!(!library.get("ALU").unwrap().is_sequential());
+
+    let chips = builtin::chips();
+    assert_eq!(chips[0].builtin(), Some("Nand"));
+    assert_eq!(n2t_hdl::parse(&n2t_hdl::to_string(&chips)).unwrap(), chips);
+}
+
+#[test]
+fn combinational() {
+    let eval = |name: &str, inputs: &[u16]| Model::new(name).unwrap().eval(inputs);
+
+    assert_eq!(eval("Nand", &[1, 1]), [0]);
+    assert_eq!(eval("Xor", &[1, 0]), [1]);
+    assert_eq!(eval("DMux", &[1, 1]), [0, 1]);
+    assert_eq!(eval("Mux16", &[7, 9, 1]), [9]);
+    assert_eq!(eval("Or8Way", &[0x100]), [0]);
+    assert_eq!(eval("Mux4Way16", &[1, 2, 3, 4, 2]), [3]);
+    assert_eq!(eval("DMux8Way", &[1, 5]), [0, 0, 0, 0, 0, 1, 0, 0]);
+    assert_eq!(eval("FullAdder", &[1, 1, 1]), [1, 1]);
+    assert_eq!(eval("Add16", &[0xffff, 2]), [1]);
+    assert_eq!(eval("Inc16", &[41]), [42]);
+
+    // x + y, x - y and !x
+    assert_eq!(eval("ALU", &[5, 3, 0, 0, 0, 0, 1, 0]), [8, 0, 0]);
+    assert_eq!(eval("ALU", &[3, 5, 0, 1, 0, 0, 1, 1]), [0xfffe, 0, 1]);
+    assert_eq!(eval("ALU", &[0xffff, 0, 0, 0, 1, 1, 0, 1]), [0, 1, 0]);
+    assert!(Model::new("Xnor").is_none());
+}
+
+#[test]
+fn sequential() {
+    let mut dff = Model::new("DFF").unwrap();
+    assert!(dff.is_sequential());
+    assert_eq!(dff.eval(&[1]), [0]);
+    dff.tick(&[1]);
+    assert_eq!(dff.eval(&[0]), [1]);
+
+    let mut ram = Model::new("RAM8").unwrap();
+    ram.tick(&[42, 1, 3]);
+    ram.tick(&[7, 0, 4]);
+    assert_eq!(ram.eval(&[0, 0, 3]), [42]);
+    assert_eq!(ram.eval(&[0, 0, 4]), [0]);
+
+    let mut pc = Model::new("PC").unwrap();
+    pc.tick(&[100, 1, 1, 0]);
+    pc.tick(&[0, 0, 1, 0]);
+    assert_eq!(pc.eval(&[]), [101]);
+    pc.tick(&[5, 1, 1, 1]);
+    assert_eq!(pc.eval(&[]), [0]);
+}
+
+#[test]
+fn project() {
+    let mut project = Project::new();
+    project.add(
+        "PC.hdl",
+        "CHIP PC {
+            IN in[16], load, inc, reset;
+            OUT out[16];
+            PARTS:
+            Inc16(in=feedback, out=incremented);
+            Mux16(a=feedback, b=incremented, sel=inc, out=w0);
+            Mux16(a=w0, b=in, sel=load, out=w1);
+            Mux16(a=w1, b=false, sel=reset, out=w2);
+            Register(in=w2, load=true, out=feedback, out=out);
+        }",
+    );
+    let library = project.resolve(&builtin::library()).unwrap();
+
+    // the PC of the project replaces the builtin one and is clocked by its register
+    let pc = project.get("PC").unwrap();
+    assert_eq!(pc.parts().len(), 5);
+    assert!(library.get("PC").unwrap().is_sequential());
+    assert_eq!(pc.check(&library), Ok(()));
+    assert_eq!(pc.check_drivers(&library), []);
+    assert_eq!(pc.check_loops(&library, &[]), []);
+}
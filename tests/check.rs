@@ -0,0 +1,139 @@
+use hdl_parser::{comphdl, n2t_hdl, shdl, ErrorKind, Library, Signal, Signature};
+
+fn library() -> Library {
+    vec![
+        Signature::new("Nand", vec!["a", "b"], vec!["out"]),
+        Signature::new_signals(
+            "Not4",
+            vec![Signal::bus("in", 4)],
+            vec![Signal::bus("out", 4)],
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn messages(errors: Vec<hdl_parser::Error>) -> Vec<(ErrorKind, String)> {
+    errors
+        .into_iter()
+        .map(|err| (err.kind().clone(), err.message().to_string()))
+        .collect()
+}
+
+#[test]
+fn n2t_hdl() {
+    let code = r"
+    CHIP Test {
+        IN a, b[4];
+        OUT out;
+        PARTS:
+        Nand(a=a, b=a, out=x);
+        Not4(in[0..1]=b[0..1], in[2]=a, in[3]=b[3], out[0]=out);
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    assert_eq!(chips[0].check(&library()), Ok(()));
+
+    let code = r"
+    CHIP Test {
+        IN a, b;
+        OUT out;
+        PARTS:
+        Nand(a=a, c=b, out=x);
+        Xor(a=a, b=b, out=y);
+        Not4(in[0..1]=false, in[1]=a, in[4]=a, out=out);
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let errors = chips[0].check(&library()).unwrap_err();
+    assert_eq!(errors[0].span(), Some(74..96));
+    assert_eq!(
+        messages(errors),
+        [
+            (
+                ErrorKind::UnknownSignal,
+                "chip <Nand> has no pin <c>".to_string()
+            ),
+            (
+                ErrorKind::UnconnectedInput,
+                "input <b> of <Nand> is not connected".to_string()
+            ),
+            (ErrorKind::UnknownChip, "unknown chip <Xor>".to_string()),
+            (
+                ErrorKind::UnknownSignal,
+                "chip <Not4> has no pin <in[4]>".to_string()
+            ),
+            (
+                ErrorKind::DuplicatePin,
+                "input <in[1]> of <Not4> is connected more than once".to_string()
+            ),
+            (
+                ErrorKind::UnconnectedInput,
+                "input <in[2..3]> of <Not4> is not connected".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn positional() {
+    let code = r"
+    component Test(a, b) -> x {
+        Nand(a, b) -> n;
+        Nand(a) -> y;
+        Not4(a, b, n, y) -> (z[0:3], w);
+        Xor(a, b) -> x;
+        x = (a, b);
+    }";
+    let chips = comphdl::parse(code).unwrap();
+    assert_eq!(
+        messages(chips[0].check(&library()).unwrap_err()),
+        [
+            (
+                ErrorKind::ArityMismatch,
+                "chip <Nand> has 2 input and 1 output bits but the part has 1 input and 1 output bits".to_string()
+            ),
+            (
+                ErrorKind::ArityMismatch,
+                "chip <Not4> has 4 input and 4 output bits but the part has 4 input and 5 output bits".to_string()
+            ),
+            (ErrorKind::UnknownChip, "unknown chip <Xor>".to_string()),
+            (
+                ErrorKind::ArityMismatch,
+                "assignment of 1 bits to 2 bits".to_string()
+            ),
+        ]
+    );
+
+    let code = r"
+    chip Test {
+        in: a, b;
+        out: x;
+        parts:
+            n = Nand(a, b);
+            x = Nand(n, n);
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+    assert_eq!(chips[0].check(&library()), Ok(()));
+}
+
+#[test]
+fn whole_buses() {
+    let code = r"
+    chip Test {
+        in: a[0..3], b;
+        out: x[0..3], y[0..3];
+        parts:
+            n = Not4(a);
+            x = Not4(n);
+            y = n;
+            m = Not4(b);
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+    assert_eq!(
+        messages(chips[0].check(&library()).unwrap_err()),
+        [(
+            ErrorKind::ArityMismatch,
+            "chip <Not4> has 4 input and 4 output bits but the part has 1 input and 4 output bits"
+                .to_string()
+        )]
+    );
+}
@@ -1,4 +1,4 @@
-use hdl_parser::{comphdl::parse, Chip, ComponentIO};
+use hdl_parser::{comphdl::parse, Chip, ComponentIO, Signal};
 
 // examples from https://badel2.github.io/comphdl/demo/v10/
 
@@ -45,7 +45,7 @@ fn or2() {
         vec![
             ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
             ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new(vec!["n_a", "n_b"], vec!["x"], "Nand"),
         ],
     );
 
@@ -68,13 +68,23 @@ fn mux_4_1() {
         Nand (sel00, sel01, sel10, sel11) -> y;
     }";
     let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
+        "Mux_4_1",
+        vec!["s1", "s0", "a", "b", "c", "d"],
+        vec!["y"],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new(vec!["s1"], vec!["d_s1"], "Buf"),
+            ComponentIO::new(vec!["s0"], vec!["d_s0"], "Buf"),
+            ComponentIO::new(vec!["s1"], vec!["n_s1"], "Not"),
+            ComponentIO::new(vec!["s0"], vec!["n_s0"], "Not"),
+            ComponentIO::new(vec!["a"], vec!["d_a"], "Buf"),
+            ComponentIO::new(vec!["b"], vec!["d_b"], "Buf"),
+            ComponentIO::new(vec!["c"], vec!["d_c"], "Buf"),
+            ComponentIO::new(vec!["d"], vec!["d_d"], "Buf"),
+            ComponentIO::new(vec!["n_s0", "n_s1", "d_a"], vec!["sel00"], "Nand"),
+            ComponentIO::new(vec!["d_s0", "n_s1", "d_b"], vec!["sel01"], "Nand"),
+            ComponentIO::new(vec!["n_s0", "d_s1", "d_c"], vec!["sel10"], "Nand"),
+            ComponentIO::new(vec!["d_s0", "d_s1", "d_d"], vec!["sel11"], "Nand"),
+            ComponentIO::new(vec!["sel00", "sel01", "sel10", "sel11"], vec!["y"], "Nand"),
         ],
     );
 
@@ -89,13 +99,12 @@ fn xor2() {
         Mux_4_1(a, b, _0, _1, _1, _0) -> x;
     }";
     let component = Chip::new(
-        "Or2",
+        "Xor2",
         vec!["a", "b"],
         vec!["x"],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new(vec![], vec!["_0", "_1", "_X"], "ConstantBit"),
+            ComponentIO::new(vec!["a", "b", "_0", "_1", "_1", "_0"], vec!["x"], "Mux_4_1"),
         ],
     );
 
@@ -110,13 +119,12 @@ fn and3() {
         Not(n_x) -> x;
     }";
     let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
+        "And3",
+        vec!["a", "b", "c"],
         vec!["x"],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new(vec!["a", "b", "c"], vec!["n_x"], "Nand"),
+            ComponentIO::new(vec!["n_x"], vec!["x"], "Not"),
         ],
     );
 
@@ -136,13 +144,19 @@ fn demux_1_4() {
         And3(d_s1, d_s0, d_i) -> f3;
     }";
     let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
+        "Demux_1_4",
+        vec!["s1", "s0", "i"],
+        vec!["f0", "f1", "f2", "f3"],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new(vec!["i"], vec!["d_i"], "Buf"),
+            ComponentIO::new(vec!["s1"], vec!["d_s1"], "Buf"),
+            ComponentIO::new(vec!["s0"], vec!["d_s0"], "Buf"),
+            ComponentIO::new(vec!["s1"], vec!["n_s1"], "Not"),
+            ComponentIO::new(vec!["s0"], vec!["n_s0"], "Not"),
+            ComponentIO::new(vec!["n_s1", "n_s0", "d_i"], vec!["f0"], "And3"),
+            ComponentIO::new(vec!["n_s1", "d_s0", "d_i"], vec!["f1"], "And3"),
+            ComponentIO::new(vec!["d_s1", "n_s0", "d_i"], vec!["f2"], "And3"),
+            ComponentIO::new(vec!["d_s1", "d_s0", "d_i"], vec!["f3"], "And3"),
         ],
     );
 
@@ -156,14 +170,21 @@ fn array_1d() {
         Nand(a[3:0], b[3:0]) -> x[1:1];
         Nand(x[1:1]) -> x[0:0];
     }";
-    let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
+    let component = Chip::new_signals(
+        "Array1D",
+        vec![Signal::slice("a", 3, 0), Signal::slice("b", 3, 0)],
+        vec![Signal::slice("x", 1, 0)],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new_signals(
+                vec![Signal::slice("a", 3, 0), Signal::slice("b", 3, 0)],
+                vec![Signal::index("x", 1)],
+                "Nand",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("x", 1)],
+                vec![Signal::index("x", 0)],
+                "Nand",
+            ),
         ],
     );
 
@@ -179,14 +200,31 @@ fn mux_16_4() {
         Mux_4_1(s[1:0], a[8:11]) -> y[2];
         Mux_4_1(s[1:0], a[12:15]) -> y[3];
     }";
-    let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
+    let component = Chip::new_signals(
+        "Mux_16_4",
+        vec![Signal::slice("s", 1, 0), Signal::slice("a", 0, 15)],
+        vec![Signal::slice("y", 0, 3)],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new_signals(
+                vec![Signal::slice("s", 1, 0), Signal::slice("a", 0, 3)],
+                vec![Signal::index("y", 0)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("s", 1, 0), Signal::slice("a", 4, 7)],
+                vec![Signal::index("y", 1)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("s", 1, 0), Signal::slice("a", 8, 11)],
+                vec![Signal::index("y", 2)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("s", 1, 0), Signal::slice("a", 12, 15)],
+                vec![Signal::index("y", 3)],
+                "Mux_4_1",
+            ),
         ],
     );
 
@@ -200,14 +238,21 @@ fn mux_16_1() {
         Mux_16_4(s[1:0], a[0:15]) -> j[0:3];
         Mux_4_1(s[3:2], j[0:3]) -> y;
     }";
-    let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
+    let component = Chip::new_signals(
+        "Mux_16_1",
+        vec![Signal::slice("s", 3, 0), Signal::slice("a", 0, 15)],
+        vec![Signal::new("y")],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new_signals(
+                vec![Signal::slice("s", 1, 0), Signal::slice("a", 0, 15)],
+                vec![Signal::slice("j", 0, 3)],
+                "Mux_16_4",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("s", 3, 2), Signal::slice("j", 0, 3)],
+                vec![Signal::new("y")],
+                "Mux_4_1",
+            ),
         ],
     );
 
@@ -220,15 +265,20 @@ fn quad() {
     component Quad(a) -> b[3:0] {
         (a, a, a, a) = b[3:0];
     }";
-    let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
-        vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
-        ],
+    let component = Chip::new_signals(
+        "Quad",
+        vec![Signal::new("a")],
+        vec![Signal::slice("b", 3, 0)],
+        vec![ComponentIO::new_signals(
+            vec![
+                Signal::new("a"),
+                Signal::new("a"),
+                Signal::new("a"),
+                Signal::new("a"),
+            ],
+            vec![Signal::slice("b", 3, 0)],
+            "",
+        )],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -243,14 +293,43 @@ fn mux_test() {
         Mux_16_1(s[3:0], aaaa[3:0], aaaa[3:0], bbbb[3:0], bbbb[3:0]) -> x;
         Mux_16_1(s[3:0], aaaa[3:0], bbbb[3:0], aaaa[3:0], bbbb[3:0]) -> y;
     }";
-    let component = Chip::new(
-        "Or2",
-        vec!["a", "b"],
-        vec!["x"],
+    let component = Chip::new_signals(
+        "MuxTest",
+        vec![Signal::slice("s", 3, 0), Signal::new("a"), Signal::new("b")],
+        vec![Signal::new("x"), Signal::new("y")],
         vec![
-            ComponentIO::new(vec!["a"], vec!["n_a"], "Not"),
-            ComponentIO::new(vec!["b"], vec!["n_b"], "Not"),
-            ComponentIO::new(vec!["a"], vec!["q"], "Nand"),
+            ComponentIO::new_signals(
+                vec![Signal::new("a")],
+                vec![Signal::slice("aaaa", 3, 0)],
+                "Quad",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("b")],
+                vec![Signal::slice("bbbb", 3, 0)],
+                "Quad",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("s", 3, 0),
+                    Signal::slice("aaaa", 3, 0),
+                    Signal::slice("aaaa", 3, 0),
+                    Signal::slice("bbbb", 3, 0),
+                    Signal::slice("bbbb", 3, 0),
+                ],
+                vec![Signal::new("x")],
+                "Mux_16_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("s", 3, 0),
+                    Signal::slice("aaaa", 3, 0),
+                    Signal::slice("bbbb", 3, 0),
+                    Signal::slice("aaaa", 3, 0),
+                    Signal::slice("bbbb", 3, 0),
+                ],
+                vec![Signal::new("y")],
+                "Mux_16_1",
+            ),
         ],
     );
 
@@ -272,10 +351,18 @@ fn multi_buf() {
         Buf60(x5) -> x6;
     }";
     let component = Chip::new(
-        "name",
+        "MultiBuf",
         vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        vec!["x1", "x2", "x3", "x4", "x5", "x6"],
+        vec![
+            ComponentIO::new(vec!["a"], vec!["x0"], "Buf"),
+            ComponentIO::new(vec!["x0"], vec!["x1"], "Buf60"),
+            ComponentIO::new(vec!["x1"], vec!["x2"], "Buf60"),
+            ComponentIO::new(vec!["x2"], vec!["x3"], "Buf60"),
+            ComponentIO::new(vec!["x3"], vec!["x4"], "Buf60"),
+            ComponentIO::new(vec!["x4"], vec!["x5"], "Buf60"),
+            ComponentIO::new(vec!["x5"], vec!["x6"], "Buf60"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -291,10 +378,15 @@ fn buf4() {
         Buf(a3) -> x;
     }";
     let component = Chip::new(
-        "name",
+        "Buf4",
         vec!["a"],
         vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        vec![
+            ComponentIO::new(vec!["a"], vec!["a1"], "Buf"),
+            ComponentIO::new(vec!["a1"], vec!["a2"], "Buf"),
+            ComponentIO::new(vec!["a2"], vec!["a3"], "Buf"),
+            ComponentIO::new(vec!["a3"], vec!["x"], "Buf"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -310,10 +402,15 @@ fn buf10() {
         Buf(a3) -> x;
     }";
     let component = Chip::new(
-        "name",
+        "Buf10",
         vec!["a"],
         vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        vec![
+            ComponentIO::new(vec!["a"], vec!["a1"], "Buf"),
+            ComponentIO::new(vec!["a1"], vec!["a2"], "Buf4"),
+            ComponentIO::new(vec!["a2"], vec!["a3"], "Buf4"),
+            ComponentIO::new(vec!["a3"], vec!["x"], "Buf"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -330,10 +427,17 @@ fn buf60() {
         Buf10(a5) -> x;
     }";
     let component = Chip::new(
-        "name",
+        "Buf60",
         vec!["a"],
         vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        vec![
+            ComponentIO::new(vec!["a"], vec!["a1"], "Buf10"),
+            ComponentIO::new(vec!["a1"], vec!["a2"], "Buf10"),
+            ComponentIO::new(vec!["a2"], vec!["a3"], "Buf10"),
+            ComponentIO::new(vec!["a3"], vec!["a4"], "Buf10"),
+            ComponentIO::new(vec!["a4"], vec!["a5"], "Buf10"),
+            ComponentIO::new(vec!["a5"], vec!["x"], "Buf10"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -356,10 +460,13 @@ fn rs_latch_raw() {
         Nand(n_R, Q) -> n_Q;
     }";
     let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        "RSLatch_raw",
+        vec!["n_R", "n_S"],
+        vec!["Q"],
+        vec![
+            ComponentIO::new(vec!["n_S", "n_Q"], vec!["Q"], "Nand"),
+            ComponentIO::new(vec!["n_R", "Q"], vec!["n_Q"], "Nand"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -375,10 +482,14 @@ fn d_latch() {
         RSLatch_raw(n_R, n_S) -> q;
     }";
     let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        "DLatch",
+        vec!["enable", "d"],
+        vec!["q"],
+        vec![
+            ComponentIO::new(vec!["enable", "d"], vec!["n_S"], "Nand"),
+            ComponentIO::new(vec!["n_S", "enable"], vec!["n_R"], "Nand"),
+            ComponentIO::new(vec!["n_R", "n_S"], vec!["q"], "RSLatch_raw"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -396,11 +507,52 @@ fn test() {
         DLatch(enable, d[1]) -> q[1];
         DLatch(enable, d[0]) -> q[0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Register8",
+        vec![Signal::new("enable"), Signal::slice("d", 7, 0)],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 7)],
+                vec![Signal::index("q", 7)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 6)],
+                vec![Signal::index("q", 6)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 5)],
+                vec![Signal::index("q", 5)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 4)],
+                vec![Signal::index("q", 4)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 3)],
+                vec![Signal::index("q", 3)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 2)],
+                vec![Signal::index("q", 2)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 1)],
+                vec![Signal::index("q", 1)],
+                "DLatch",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::index("d", 0)],
+                vec![Signal::index("q", 0)],
+                "DLatch",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -415,11 +567,32 @@ fn register32() {
         Register8(enable, d[15:8]) -> q[15:8];
         Register8(enable, d[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Register32",
+        vec![Signal::new("enable"), Signal::slice("d", 31, 0)],
+        vec![Signal::slice("q", 31, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::slice("d", 31, 24)],
+                vec![Signal::slice("q", 31, 24)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::slice("d", 23, 16)],
+                vec![Signal::slice("q", 23, 16)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::slice("d", 15, 8)],
+                vec![Signal::slice("q", 15, 8)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("enable"), Signal::slice("d", 7, 0)],
+                vec![Signal::slice("q", 7, 0)],
+                "Register8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -437,11 +610,106 @@ fn mux_32_8() {
         Mux_4_1(sel[1:0], a[6], b[6], c[6], d[6]) -> y[6];
         Mux_4_1(sel[1:0], a[7], b[7], c[7], d[7]) -> y[7];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Mux_32_8",
+        vec![
+            Signal::slice("sel", 1, 0),
+            Signal::slice("a", 7, 0),
+            Signal::slice("b", 7, 0),
+            Signal::slice("c", 7, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("y", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 0),
+                    Signal::index("b", 0),
+                    Signal::index("c", 0),
+                    Signal::index("d", 0),
+                ],
+                vec![Signal::index("y", 0)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 1),
+                    Signal::index("b", 1),
+                    Signal::index("c", 1),
+                    Signal::index("d", 1),
+                ],
+                vec![Signal::index("y", 1)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 2),
+                    Signal::index("b", 2),
+                    Signal::index("c", 2),
+                    Signal::index("d", 2),
+                ],
+                vec![Signal::index("y", 2)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 3),
+                    Signal::index("b", 3),
+                    Signal::index("c", 3),
+                    Signal::index("d", 3),
+                ],
+                vec![Signal::index("y", 3)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 4),
+                    Signal::index("b", 4),
+                    Signal::index("c", 4),
+                    Signal::index("d", 4),
+                ],
+                vec![Signal::index("y", 4)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 5),
+                    Signal::index("b", 5),
+                    Signal::index("c", 5),
+                    Signal::index("d", 5),
+                ],
+                vec![Signal::index("y", 5)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 6),
+                    Signal::index("b", 6),
+                    Signal::index("c", 6),
+                    Signal::index("d", 6),
+                ],
+                vec![Signal::index("y", 6)],
+                "Mux_4_1",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("sel", 1, 0),
+                    Signal::index("a", 7),
+                    Signal::index("b", 7),
+                    Signal::index("c", 7),
+                    Signal::index("d", 7),
+                ],
+                vec![Signal::index("y", 7)],
+                "Mux_4_1",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -457,11 +725,52 @@ fn ram4x8() {
         Register8(w[3], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[1:0], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram4x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 1, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 1, 0), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("w", 0), Signal::slice("d", 7, 0)],
+                vec![Signal::slice("qa", 7, 0)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("w", 1), Signal::slice("d", 7, 0)],
+                vec![Signal::slice("qb", 7, 0)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("w", 2), Signal::slice("d", 7, 0)],
+                vec![Signal::slice("qc", 7, 0)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("w", 3), Signal::slice("d", 7, 0)],
+                vec![Signal::slice("qd", 7, 0)],
+                "Register8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 1, 0),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -477,11 +786,68 @@ fn ram16x8() {
         Ram4x8(w[3], addr[1:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[3:2], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram16x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 3, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 3, 2), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 1, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram4x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 1, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram4x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 1, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram4x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 1, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram4x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 3, 2),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -497,11 +863,68 @@ fn ram64x8() {
         Ram16x8(w[3], addr[3:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[5:4], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram64x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 5, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 5, 4), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 3, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram16x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 3, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram16x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 3, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram16x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 3, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram16x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 5, 4),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -517,11 +940,68 @@ fn ram256x8() {
         Ram64x8(w[3], addr[5:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[7:6], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram256x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 7, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 7, 6), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 5, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram64x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 5, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram64x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 5, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram64x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 5, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram64x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 7, 6),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -537,11 +1017,68 @@ fn ram1024x8() {
         Ram256x8(w[3], addr[7:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[9:8], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram1024x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 9, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 9, 8), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 7, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram256x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 7, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram256x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 7, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram256x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 7, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram256x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 9, 8),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -558,11 +1095,68 @@ fn ram4096x8() {
         Ram1024x8(w[3], addr[9:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[11:10], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram4096x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 11, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 11, 10), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 9, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram1024x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 9, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram1024x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 9, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram1024x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 9, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram1024x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 11, 10),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -579,11 +1173,68 @@ fn ram16384x8() {
         Ram4096x8(w[3], addr[11:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[13:12], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram16384x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 13, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 13, 12), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 11, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram4096x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 11, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram4096x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 11, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram4096x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 11, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram4096x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 13, 12),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -599,11 +1250,68 @@ fn ram65536x8() {
         Ram16384x8(w[3], addr[13:0], d[7:0]) -> qd[7:0];
         Mux_32_8(addr[15:14], qa[7:0], qb[7:0], qc[7:0], qd[7:0]) -> q[7:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Ram65536x8",
+        vec![
+            Signal::new("write"),
+            Signal::slice("addr", 15, 0),
+            Signal::slice("d", 7, 0),
+        ],
+        vec![Signal::slice("q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::slice("addr", 15, 14), Signal::new("write")],
+                vec![Signal::slice("w", 0, 3)],
+                "Demux_1_4",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 0),
+                    Signal::slice("addr", 13, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qa", 7, 0)],
+                "Ram16384x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 1),
+                    Signal::slice("addr", 13, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qb", 7, 0)],
+                "Ram16384x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 2),
+                    Signal::slice("addr", 13, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qc", 7, 0)],
+                "Ram16384x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::index("w", 3),
+                    Signal::slice("addr", 13, 0),
+                    Signal::slice("d", 7, 0),
+                ],
+                vec![Signal::slice("qd", 7, 0)],
+                "Ram16384x8",
+            ),
+            ComponentIO::new_signals(
+                vec![
+                    Signal::slice("addr", 15, 14),
+                    Signal::slice("qa", 7, 0),
+                    Signal::slice("qb", 7, 0),
+                    Signal::slice("qc", 7, 0),
+                    Signal::slice("qd", 7, 0),
+                ],
+                vec![Signal::slice("q", 7, 0)],
+                "Mux_32_8",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -619,10 +1327,13 @@ fn ns_nr_latch() {
         Nand(n_R, Q) -> n_Q;
     }";
     let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        "nSnRLatch",
+        vec!["n_S", "n_R"],
+        vec!["Q", "n_Q"],
+        vec![
+            ComponentIO::new(vec!["n_S", "n_Q"], vec!["Q"], "Nand"),
+            ComponentIO::new(vec!["n_R", "Q"], vec!["n_Q"], "Nand"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -636,10 +1347,14 @@ fn sr_latch() {
         nSnRLatch(n_S, n_R) -> (Q, n_Q);
     }";
     let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        "SRLatch",
+        vec!["E", "S", "R"],
+        vec!["Q"],
+        vec![
+            ComponentIO::new(vec!["E", "S"], vec!["n_S"], "Nand"),
+            ComponentIO::new(vec!["E", "R"], vec!["n_R"], "Nand"),
+            ComponentIO::new(vec!["n_S", "n_R"], vec!["Q", "n_Q"], "nSnRLatch"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -655,10 +1370,15 @@ fn reg4() {
         DLatch(E, D0) -> Q0;
     }";
     let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        "Reg4",
+        vec!["E", "D3", "D2", "D1", "D0"],
+        vec!["Q3", "Q2", "Q1", "Q0"],
+        vec![
+            ComponentIO::new(vec!["E", "D3"], vec!["Q3"], "DLatch"),
+            ComponentIO::new(vec!["E", "D2"], vec!["Q2"], "DLatch"),
+            ComponentIO::new(vec!["E", "D1"], vec!["Q1"], "DLatch"),
+            ComponentIO::new(vec!["E", "D0"], vec!["Q0"], "DLatch"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -670,11 +1390,22 @@ fn reg8() {
         Reg4(E, D[7:4]) -> Q[7:4];
         Reg4(E, D[3:0]) -> Q[3:0];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Reg8",
+        vec![Signal::new("E"), Signal::slice("D", 7, 0)],
+        vec![Signal::slice("Q", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::new("E"), Signal::slice("D", 7, 4)],
+                vec![Signal::slice("Q", 7, 4)],
+                "Reg4",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("E"), Signal::slice("D", 3, 0)],
+                vec![Signal::slice("Q", 3, 0)],
+                "Reg4",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -689,11 +1420,22 @@ fn catv0() {
         Stdin(clk) -> (eof, x[7:0]);
         Stdout(clk, x[7:0]);
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Catv0",
+        vec![Signal::new("clk")],
+        vec![Signal::new("eof")],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::new("clk")],
+                vec![Signal::new("eof"), Signal::slice("x", 7, 0)],
+                "Stdin",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("clk"), Signal::slice("x", 7, 0)],
+                vec![],
+                "Stdout",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -717,11 +1459,58 @@ fn cat() {
         Bufw8(x2[7:0]) -> x3[7:0];
         Stdout(clk2, x3[7:0]);
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Cat",
+        vec![Signal::new("enable")],
+        vec![Signal::new("eof3")],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::new("enable")],
+                vec![Signal::new("clk")],
+                "Clk2",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("clk")],
+                vec![Signal::new("eof"), Signal::slice("x", 7, 0)],
+                "Stdin",
+            ),
+            ComponentIO::new_signals(vec![Signal::new("eof")], vec![Signal::new("eof1")], "Buf"),
+            ComponentIO::new_signals(vec![Signal::new("eof1")], vec![Signal::new("eof2")], "Buf"),
+            ComponentIO::new_signals(vec![Signal::new("eof2")], vec![Signal::new("eof3")], "Buf"),
+            ComponentIO::new_signals(vec![Signal::new("eof")], vec![Signal::new("neof")], "Nand"),
+            ComponentIO::new_signals(vec![Signal::new("clk")], vec![Signal::new("bclk1")], "Buf"),
+            ComponentIO::new_signals(vec![Signal::new("bclk1")], vec![Signal::new("bclk")], "Buf"),
+            ComponentIO::new_signals(
+                vec![Signal::new("neof"), Signal::new("bclk")],
+                vec![Signal::new("nclk2")],
+                "Nand",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("nclk2")],
+                vec![Signal::new("clk2")],
+                "Nand",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("x", 7, 0)],
+                vec![Signal::slice("x1", 7, 0)],
+                "Bufw8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("x1", 7, 0)],
+                vec![Signal::slice("x2", 7, 0)],
+                "Bufw8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::slice("x2", 7, 0)],
+                vec![Signal::slice("x3", 7, 0)],
+                "Bufw8",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::new("clk2"), Signal::slice("x3", 7, 0)],
+                vec![],
+                "Stdout",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -740,11 +1529,52 @@ fn bufw8() {
         Buf(a[6]) -> x[6];
         Buf(a[7]) -> x[7];
     }";
-    let component = Chip::new(
-        "name",
-        vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+    let component = Chip::new_signals(
+        "Bufw8",
+        vec![Signal::slice("a", 7, 0)],
+        vec![Signal::slice("x", 7, 0)],
+        vec![
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 0)],
+                vec![Signal::index("x", 0)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 1)],
+                vec![Signal::index("x", 1)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 2)],
+                vec![Signal::index("x", 2)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 3)],
+                vec![Signal::index("x", 3)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 4)],
+                vec![Signal::index("x", 4)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 5)],
+                vec![Signal::index("x", 5)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 6)],
+                vec![Signal::index("x", 6)],
+                "Buf",
+            ),
+            ComponentIO::new_signals(
+                vec![Signal::index("a", 7)],
+                vec![Signal::index("x", 7)],
+                "Buf",
+            ),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -760,10 +1590,15 @@ fn clk4() {
         Buf(x2) -> x3;
     }";
     let component = Chip::new(
-        "name",
+        "Clk4",
         vec!["a"],
-        vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        vec!["x1"],
+        vec![
+            ComponentIO::new(vec!["a", "x3"], vec!["x"], "Nand"),
+            ComponentIO::new(vec!["x"], vec!["x1"], "Nand"),
+            ComponentIO::new(vec!["x1"], vec!["x2"], "Nand"),
+            ComponentIO::new(vec!["x2"], vec!["x3"], "Buf"),
+        ],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
@@ -775,10 +1610,10 @@ fn clk2() {
         Nand(a, x) -> x;
     }";
     let component = Chip::new(
-        "name",
+        "Clk2",
         vec!["a"],
         vec!["x"],
-        vec![ComponentIO::new(vec!["a"], vec!["b"], "name")],
+        vec![ComponentIO::new(vec!["a", "x"], vec!["x"], "Nand")],
     );
 
     assert_eq!(parse(code), Ok(vec![component]));
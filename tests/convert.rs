@@ -0,0 +1,211 @@
+use hdl_parser::{
+    comphdl, n2t_hdl, shdl, Chip, Component, ComponentIO, ComponentMap, ErrorKind, Library, Signal,
+    Signature,
+};
+
+fn library() -> Library {
+    vec![
+        Signature::new("Nand", vec!["a", "b"], vec!["out"]),
+        Signature::new("Not", vec!["in"], vec!["out"]),
+        Signature::new("HalfAdder", vec!["a", "b"], vec!["sum", "carry"]),
+        Signature::new_signals(
+            "Not4",
+            vec![Signal::bus("in", 4)],
+            vec![Signal::bus("out", 4)],
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn n2t_hdl_to_shdl() {
+    let code = r"
+    CHIP And {
+        IN a, b;
+        OUT out;
+        PARTS:
+        Nand(b=b, a=a, out=n);
+        Not(in=n, out=out);
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let chip = chips[0].to_component_io(&library()).unwrap();
+
+    assert_eq!(
+        chip,
+        Chip::new(
+            "And",
+            vec!["a", "b"],
+            vec!["out"],
+            vec![
+                ComponentIO::new(vec!["a", "b"], vec!["n"], "Nand"),
+                ComponentIO::new(vec!["n"], vec!["out"], "Not"),
+            ]
+        )
+    );
+    assert_eq!(chip.parts()[1].span(), chips[0].parts()[1].span());
+
+    let printed = shdl::to_string(&(vec![chip.clone()], Vec::new()));
+    assert_eq!(shdl::parse(&printed).unwrap(), (vec![chip], Vec::new()));
+}
+
+#[test]
+fn unconnected_pins() {
+    let code = r"
+    CHIP Half {
+        IN a;
+        OUT x, y, c;
+        PARTS:
+        Nand(a=a, out=x, out=y);
+        HalfAdder(a=a, b=a, carry=c);
+        HalfAdder(a=a, b=a, sum=unused_sum);
+    }";
+    let chip = n2t_hdl::parse(code).unwrap()[0]
+        .to_component_io(&library())
+        .unwrap();
+
+    assert_eq!(
+        chip.parts(),
+        [
+            ComponentIO::new(vec!["a", "false"], vec!["x"], "Nand"),
+            ComponentIO::new(vec!["x"], vec!["y"], ""),
+            ComponentIO::new(vec!["a", "a"], vec!["unused_sum1", "c"], "HalfAdder"),
+            ComponentIO::new(vec!["a", "a"], vec!["unused_sum"], "HalfAdder"),
+        ]
+    );
+}
+
+#[test]
+fn shdl_to_n2t_hdl() {
+    let code = r"
+    chip A {
+        in: a, b;
+        out: x, y;
+        parts:
+            n = Nand(a, b);
+            x = n;
+            m = a;
+            k = m;
+            y = Not(k);
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+    let chip = chips[0].to_component_map(&library()).unwrap();
+
+    assert_eq!(
+        chip,
+        Chip::new(
+            "A",
+            vec!["a", "b"],
+            vec!["x", "y"],
+            vec![
+                ComponentMap::new(
+                    vec![("a", "a"), ("b", "b"), ("out", "n"), ("out", "x")],
+                    "Nand"
+                ),
+                ComponentMap::new(vec![("in", "a"), ("out", "y")], "Not"),
+            ]
+        )
+    );
+    let chips = vec![chip];
+    let printed = n2t_hdl::to_string(&chips);
+    assert_eq!(n2t_hdl::parse(&printed).unwrap(), chips);
+}
+
+#[test]
+fn whole_buses() {
+    let code = r"
+    chip Test {
+        in: a[0..3];
+        out: x[0..3], y[0..3];
+        parts:
+            n = Not4(a);
+            x = Not4(n);
+            y = n;
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+    let chip = chips[0].to_component_map(&library()).unwrap();
+
+    assert_eq!(
+        chip.parts(),
+        [
+            ComponentMap::new(vec![("in", "a"), ("out", "n"), ("out", "y")], "Not4"),
+            ComponentMap::new(vec![("in", "n"), ("out", "x")], "Not4"),
+        ]
+    );
+}
+
+#[test]
+fn comphdl_to_n2t_hdl() {
+    let code = r"
+    component Half(a, b) -> (s, c) {
+        HalfAdder(a, b) -> (s, c);
+    }";
+    let chip = comphdl::parse(code).unwrap()[0]
+        .to_component_map(&library())
+        .unwrap();
+
+    assert_eq!(
+        chip.parts(),
+        [ComponentMap::new(
+            vec![("a", "a"), ("b", "b"), ("sum", "s"), ("carry", "c")],
+            "HalfAdder"
+        )]
+    );
+}
+
+#[test]
+fn component() {
+    let part = ComponentMap::new(vec![("out", "x"), ("a", "a"), ("b", "b")], "Nand");
+    let component = Component::from_map(&part, &library()).unwrap();
+
+    assert_eq!(
+        component,
+        Component::new(vec![("a", "a"), ("b", "b")], vec![("out", "x")], "Nand")
+    );
+    assert_eq!(
+        ComponentMap::from(&component),
+        ComponentMap::new(vec![("a", "a"), ("b", "b"), ("out", "x")], "Nand")
+    );
+
+    let part = ComponentIO::new(vec!["a", "b"], vec!["x"], "Nand");
+    assert_eq!(Component::from_io(&part, &library()).unwrap(), component);
+}
+
+#[test]
+fn errors() {
+    let library = library();
+    let kind = |code: &str| {
+        let (chips, _) = shdl::parse(code).unwrap();
+        chips[0]
+            .to_component_map(&library)
+            .unwrap_err()
+            .kind()
+            .clone()
+    };
+
+    assert_eq!(
+        kind("chip A { in: a; out: x; parts: x = Or(a, a); }"),
+        ErrorKind::UnknownChip
+    );
+    assert_eq!(
+        kind("chip A { in: a; out: x; parts: x = Nand(a); }"),
+        ErrorKind::ArityMismatch
+    );
+    assert_eq!(
+        kind("chip A { in: a; out: x; parts: x = a; }"),
+        ErrorKind::Unsupported
+    );
+    assert_eq!(
+        kind("chip A { in: a; out: x; parts: x, n = a; }"),
+        ErrorKind::ArityMismatch
+    );
+
+    let part = ComponentMap::new(vec![("a", "a"), ("c", "b"), ("out", "x")], "Nand");
+    let err = Component::from_map(&part, &library).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnknownSignal);
+    assert_eq!(err.to_string(), "chip <Nand> has no pin <c>");
+
+    let part = ComponentIO::new(vec!["a"], vec!["x"], "");
+    let err = Component::from_io(&part, &library).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Unsupported);
+}
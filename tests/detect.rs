@@ -0,0 +1,97 @@
+use hdl_parser::{
+    comphdl, detect, n2t_hdl, open_gal, parse_any, shdl, Dialect, ErrorKind, HdlParse,
+};
+
+#[test]
+fn dialects() {
+    let code = "// nand2tetris\nCHIP Not {\n    IN in;\n    OUT out;\n    PARTS:\n    Nand(a=in, b=in, out=out);\n}";
+    assert_eq!(
+        parse_any(code).unwrap(),
+        HdlParse::N2tHdl(n2t_hdl::parse(code).unwrap())
+    );
+
+    let code = "/* shdl */ chip Not { in: a; out: x; func: x = !a; }";
+    assert_eq!(
+        parse_any(code).unwrap(),
+        HdlParse::Shdl(shdl::parse(code).unwrap())
+    );
+
+    let code = "component Not(a) -> x { Nand(a, a) -> x; }";
+    let result = parse_any(code).unwrap();
+    assert_eq!(result, HdlParse::Comphdl(comphdl::parse(code).unwrap()));
+    assert_eq!(result.dialect(), Dialect::Comphdl);
+
+    for code in [
+        "pin 1, 2 = a, b;\nx = a & b;",
+        "table(a -> x) { 0 1 1 0 }\npin 1 = a;",
+        "x = a & b;\nx.dff;",
+    ] {
+        assert_eq!(
+            parse_any(code).unwrap(),
+            HdlParse::OpenGal(open_gal::parse(code).unwrap())
+        );
+    }
+}
+
+#[test]
+fn keywords_as_names() {
+    // only the keywords that start a statement are used
+    let code =
+        "chip A {\n    in: pin, table;\n    out: component;\n    func: component = pin & table;\n}";
+    assert_eq!(detect(code).unwrap(), Dialect::Shdl);
+
+    let code = "pin 1 = chip;\nCHIP = chip;";
+    assert_eq!(detect(code).unwrap(), Dialect::OpenGal);
+}
+
+#[test]
+fn parse_error() {
+    let code = "CHIP Not {\n    IN in\n    OUT out;\n}";
+    assert_eq!(
+        parse_any(code).unwrap_err(),
+        n2t_hdl::parse(code).unwrap_err()
+    );
+}
+
+#[test]
+fn unknown() {
+    let err = parse_any("").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnknownDialect);
+    assert_eq!(err.to_string(), "unknown dialect the file is empty");
+
+    let err = parse_any("// comment\nmodule And(a, b);").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::UnknownDialect);
+    assert_eq!(
+        err.to_string(),
+        "line 2: unknown dialect expected <CHIP>, <chip>, <component>, <pin> or <table> but got <module>"
+    );
+}
+
+#[test]
+fn ambiguous() {
+    let code = "CHIP Not {\n    IN in;\n    OUT out;\n    PARTS:\n}\n\ncomponent And(a, b) -> x {}";
+    let err = parse_any(code).unwrap_err();
+
+    assert_eq!(err.kind(), &ErrorKind::AmbiguousDialect);
+    assert_eq!(
+        err.to_string(),
+        "line 7: ambiguous dialect <CHIP> on line 1 is n2t_hdl but <component> is comphdl"
+    );
+    assert_eq!(err.span(), Some(49..58));
+}
+
+#[test]
+fn crlf() {
+    let code = "// nand2tetris\r\n\r\nCHIP Not {\r\n    IN in;\r\n    OUT out;\r\n\r\n    PARTS:\r\n    Nand(a=in, b=in, out=out);\r\n}\r\n";
+    assert_eq!(detect(code).unwrap(), Dialect::N2tHdl);
+    assert_eq!(
+        parse_any(code).unwrap(),
+        HdlParse::N2tHdl(n2t_hdl::parse(code).unwrap())
+    );
+
+    let err = parse_any("\r\n\r\n# And").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3: unknown dialect expected <CHIP>, <chip>, <component>, <pin> or <table> but got <#>"
+    );
+}
@@ -0,0 +1,129 @@
+use hdl_parser::{comphdl, n2t_hdl, shdl, ErrorKind, Library, Signal, Signature};
+
+fn library() -> Library {
+    vec![
+        Signature::new("Nand", vec!["a", "b"], vec!["out"]),
+        Signature::new_signals(
+            "Not4",
+            vec![Signal::bus("in", 4)],
+            vec![Signal::bus("out", 4)],
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn messages(errors: &[hdl_parser::Error]) -> Vec<(ErrorKind, &str)> {
+    errors
+        .iter()
+        .map(|err| (err.kind().clone(), err.message()))
+        .collect()
+}
+
+#[test]
+fn n2t_hdl() {
+    let code = r"
+    CHIP Test {
+        IN a, b[4];
+        OUT out[4], carry;
+        PARTS:
+        Nand(a=a, b=x, out=y);
+        Not4(in=b, out=out, out[0]=carry);
+        Nand(a=a, b=y, out=out[3]);
+        Nand(a=a, b=true, out=b[1]);
+        Not4(in=b, out[2..3]=z);
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let errors = chips[0].check_drivers(&library());
+
+    assert_eq!(
+        messages(&errors),
+        [
+            (ErrorKind::UndrivenSignal, "<x> is used but not driven"),
+            (
+                ErrorKind::MultipleDrivers,
+                "<out[3]> has more than one driver"
+            ),
+            (
+                ErrorKind::InputDriven,
+                "input <b[1]> of the chip is driven by a part"
+            ),
+            (
+                ErrorKind::DanglingSignal,
+                "<z[0..1]> is driven but never used"
+            ),
+        ]
+    );
+    assert_eq!(errors[0].span(), Some(87..109));
+    assert!(!errors[2].is_warning());
+    assert!(errors[3].is_warning());
+
+    let code = "CHIP Test { IN a; OUT out; PARTS: Xor(a=a, b=a, out=out); }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let errors = chips[0].check_drivers(&library());
+    assert_eq!(
+        messages(&errors),
+        [(ErrorKind::UnknownChip, "unknown chip <Xor>")]
+    );
+}
+
+#[test]
+fn comphdl() {
+    let code = r"
+    component Test(a[3:0], en) -> (x[1:0], y) {
+        Not4(a[3:0]) -> n[0:3];
+        n[0:1] = x[1:0];
+    }";
+    let chips = comphdl::parse(code).unwrap();
+    let errors = chips[0].check_drivers(&library());
+
+    assert_eq!(
+        messages(&errors),
+        [
+            (ErrorKind::UndrivenSignal, "output <y> is not driven"),
+            (
+                ErrorKind::DanglingSignal,
+                "<n[2..3]> is driven but never used"
+            ),
+        ]
+    );
+    assert_eq!(
+        errors[1].render("test.comphdl", code),
+        "warning: <n[2..3]> is driven but never used
+ --> test.comphdl:3:9
+  |
+3 |         Not4(a[3:0]) -> n[0:3];
+  |         ^^^^^^^^^^^^^^^^^^^^^^^
+"
+    );
+
+    let code = "component Id(a) -> x { a = x; }";
+    assert!(comphdl::parse(code).unwrap()[0]
+        .check_drivers(&library())
+        .is_empty());
+}
+
+#[test]
+fn whole_buses() {
+    let code = r"
+    chip Test {
+        in: a[0..3];
+        out: x[0..3], y[0..3];
+        parts:
+            x = Not4(a);
+            n = Not4(a);
+            y[0..1] = n[0..1];
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+
+    assert_eq!(
+        messages(&chips[0].check_drivers(&library())),
+        [
+            (ErrorKind::UndrivenSignal, "output <y[2..3]> is not driven"),
+            (
+                ErrorKind::DanglingSignal,
+                "<n[2..3]> is driven but never used"
+            ),
+        ]
+    );
+}
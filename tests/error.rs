@@ -0,0 +1,274 @@
+use hdl_parser::{comphdl, n2t_hdl, open_gal, shdl, ErrorKind};
+
+#[test]
+fn display() {
+    let code = "CHIP Not {\n    IN in\n    OUT out;\n}";
+    let err = n2t_hdl::parse(code).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "line 3: unexpected token expected <Semicolon> but got <Out>"
+    );
+    let _: &dyn std::error::Error = &err;
+}
+
+#[test]
+fn render() {
+    let code = "CHIP Not {\n    IN in;\n    OUT out@;\n}";
+    let err = n2t_hdl::parse(code).unwrap_err();
+
+    assert_eq!(
+        err.render("not.hdl", code),
+        "error: invalid character <@>
+ --> not.hdl:3:12
+  |
+3 |     OUT out@;
+  |            ^
+"
+    );
+}
+
+#[test]
+fn render_span() {
+    let code = "CHIP Not {\n\tIN in;\n\tOUT out;\n\tPARTS:\n\tNot16(in=in, out=out)\n}";
+    let err = n2t_hdl::parse(code).unwrap_err();
+
+    assert_eq!(
+        err.render("not.hdl", code),
+        "error: unexpected token expected <Semicolon> but got <CloseC>
+ --> not.hdl:6:1
+  |
+6 | }
+  | ^
+"
+    );
+
+    let code = "CHIP Not {\n\tIN in[8];\n\tOUT out[8];\n\tPARTS:\n\tNot8(in=in, out[0..3]=out);\n}";
+    let err = n2t_hdl::parse(code).unwrap_err();
+    assert_eq!(
+        err.render("not.hdl", code),
+        "error: width mismatch <out> has 4 bits but <out> has 8 bits
+ --> not.hdl:5:14
+  |
+5 | \tNot8(in=in, out[0..3]=out);
+  | \t            ^^^
+"
+    );
+}
+
+#[test]
+fn render_eof() {
+    let code = "CHIP Not {\n    IN in;";
+    let err = n2t_hdl::parse(code).unwrap_err();
+
+    assert_eq!(
+        err.render("not.hdl", code),
+        "error: unexpected end of file expected token <Parts> or <Builtin>
+ --> not.hdl:2:11
+  |
+2 |     IN in;
+  |           ^
+"
+    );
+}
+
+#[test]
+fn render_crlf() {
+    // the offset of the end of the file is past the text without the `\r`
+    let code = "CHIP Foo {\r";
+    let err = n2t_hdl::parse(code).unwrap_err();
+
+    assert_eq!(
+        err.render("foo.hdl", code),
+        "error: unexpected end of file expected token <Parts> or <Builtin>
+ --> foo.hdl:1:12
+  |
+1 | CHIP Foo {
+  |           ^
+"
+    );
+}
+
+#[test]
+fn json() {
+    let code = "CHIP Not {\n    IN in;\n    OUT \"out\";\n}";
+    let err = n2t_hdl::parse(code).unwrap_err();
+
+    assert_eq!(
+        err.to_json("dir/not.hdl", code),
+        r#"{"file":"dir/not.hdl","kind":"invalid_character","message":"invalid character <\">","line":3,"column":9,"start":30,"end":31}"#
+    );
+
+    let err = n2t_hdl::parse("CHIP").unwrap_err();
+    assert_eq!(
+        err.to_json("not.hdl", "CHIP"),
+        r#"{"file":"not.hdl","kind":"unexpected_eof","message":"unexpected end of file expected token <Identifier(\"\")>","line":1,"column":5,"start":null,"end":null}"#
+    );
+}
+
+fn kinds(errors: Vec<hdl_parser::Error>) -> Vec<ErrorKind> {
+    errors.iter().map(|err| err.kind().clone()).collect()
+}
+
+#[test]
+fn recover_n2t_hdl() {
+    let code = r"
+    CHIP And {
+        IN a, b;
+        OUT out;
+        PARTS:
+        Nand(a=a b=b, out=n);
+        Nand(a=n, b=n, out=out);
+        Not(in=n out=x);
+    }
+    CHIP Or {
+        IN a b;
+        OUT out;
+        PARTS:
+        Nand(a=a, b=b, out=out);
+    }
+    CHIP Not16 {
+        IN in[16];
+        OUT out[8];
+        PARTS:
+        Not8(in=in[0..7], out[0..3]=out);
+        Not8(in=in[8..15], out=@);
+    }";
+
+    let errors = n2t_hdl::parse_recover(code).unwrap_err();
+    assert_eq!(
+        kinds(errors.clone()),
+        vec![
+            ErrorKind::UnexpectedToken,
+            ErrorKind::UnexpectedToken,
+            ErrorKind::UnexpectedToken,
+            ErrorKind::WidthMismatch,
+            ErrorKind::InvalidCharacter("@".to_string()),
+            ErrorKind::UnexpectedToken,
+        ]
+    );
+    assert_eq!(
+        errors.iter().map(|err| err.to_string()).collect::<Vec<_>>()[..3],
+        [
+            "line 6: unexpected token expected <CloseP> but got <Identifier(\"b\")>",
+            "line 8: unexpected token expected <CloseP> but got <Identifier(\"out\")>",
+            "line 11: unexpected token expected <Semicolon> but got <Identifier(\"b\")>",
+        ]
+    );
+    assert_eq!(n2t_hdl::parse(code).unwrap_err(), errors[0]);
+}
+
+#[test]
+fn recover_comphdl() {
+    let code = r"
+    component And(a, b) -> x {
+        Nand(a b) -> n;
+        Nand(n, n) -> x
+    }
+    component Or(a, b) -> x {
+        x = a b;
+    }";
+
+    assert_eq!(
+        kinds(comphdl::parse_recover(code).unwrap_err()),
+        vec![ErrorKind::UnexpectedToken; 3]
+    );
+}
+
+#[test]
+fn recover_shdl() {
+    let code = r"
+    chip Half {
+        in: a, b;
+        out: s, c;
+        func:
+            s = a ^ ;
+            c = a & d;
+            s = a;
+            x = b;
+    }
+    chip Not {
+        in: a;
+        out: x;
+        full:
+            0 1
+    }
+    chip Xor {
+        in: a, b;
+        out: x;
+        func:
+            x = a ^ b;
+    }";
+
+    assert_eq!(
+        kinds(shdl::parse_recover(code).unwrap_err()),
+        vec![
+            ErrorKind::UnexpectedToken,
+            ErrorKind::UnknownSignal,
+            ErrorKind::UnknownSignal,
+            ErrorKind::InvalidTable,
+        ]
+    );
+}
+
+#[test]
+fn recover_open_gal() {
+    let code = r"
+    pin 1, 2 = a, b;
+    pin 3 = a;
+    pin [4..5] = x;
+    table(a, b -> x) {
+        00 0
+        00 1
+    }
+    y = a & ;
+    x.dff;";
+
+    assert_eq!(
+        kinds(open_gal::parse_recover(code).unwrap_err()),
+        vec![
+            ErrorKind::DuplicatePin,
+            ErrorKind::WidthMismatch,
+            ErrorKind::InvalidTable,
+            ErrorKind::UnexpectedToken,
+        ]
+    );
+}
+
+#[test]
+fn crlf() {
+    let crlf = |code: &str| code.replace('\n', "\r\n");
+
+    let code =
+        crlf("CHIP Not {\n    IN in;\n    OUT out;\n    PARTS:\n    Nand(a=in, b=in, out=out);\n}");
+    assert!(n2t_hdl::parse(&code).is_ok());
+    let code = crlf("component Not(a) -> b {\n    Nand(a, a) -> b;\n}");
+    assert!(comphdl::parse(&code).is_ok());
+    let code = crlf("chip Not {\n    in: a;\n    out: b;\n    parts:\n    b = Nand(a, a);\n}");
+    assert!(shdl::parse(&code).is_ok());
+    let code = crlf("pin 1 = a;\npin 2 = b;\nb = !a;");
+    assert!(open_gal::parse(&code).is_ok());
+
+    // the line of an error still counts the newlines
+    let code = crlf("CHIP Not {\n    IN in\n    OUT out;\n}");
+    let err = n2t_hdl::parse(&code).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3: unexpected token expected <Semicolon> but got <Out>"
+    );
+}
+
+#[test]
+fn number_too_large() {
+    // a number that does not fit into usize is too large and not an invalid character
+    let huge = "99999999999999999999";
+    let errors = [
+        n2t_hdl::parse(&format!("CHIP A {{ IN a[{}]; OUT b; PARTS: }}", huge)).unwrap_err(),
+        comphdl::parse(&format!("component A(a[{}:0]) -> b {{}}", huge)).unwrap_err(),
+        open_gal::parse(&format!("pin {} = a;", huge)).unwrap_err(),
+        shdl::parse(&format!("chip A {{ in: a[{}]; out: b; parts: }}", huge)).unwrap_err(),
+    ];
+    for err in errors {
+        assert_eq!(err.kind(), &ErrorKind::NumberTooLarge, "{}", err);
+    }
+}
@@ -0,0 +1,275 @@
+use hdl_parser::{comphdl, n2t_hdl, open_gal, shdl};
+
+// every parser has to return either Ok or Err for any input, these tests check that
+// nothing panics, overflows the stack or hangs and that printing a result round-trips
+
+const COMPHDL: &str = r"
+component And(a, b) -> x {
+    Nand(a, b) -> n; // nand
+    Nand(n, n) -> x;
+}
+component Bus(a[3:0]) -> (b[0:3], c) {
+    b[0:3] = a[3:0];
+    /* block */ c = a[2];
+}";
+
+const SHDL: &str = r"
+chip And {
+    in: a, b;
+    out: x;
+    parts:
+        n = Nand(a, b);
+        x = Not(n);
+}
+chip Half {
+    in: a, b;
+    out: s, c;
+    func:
+        s = a ^ b;
+        c = !(!a | !b) & (a);
+}
+chip Count {
+    in: a, b;
+    out: x;
+    count: 0110
+}";
+
+const OPEN_GAL: &str = r"
+pin 1, 2 = i[0..1];
+pin [13..14] = and, or;
+table(i0, i1 -> and).fill(0) {
+    11 1
+}
+table(i0, i1 -> or).count {
+    0111
+}
+a = (!b | (c)) & b ^ c;
+a.dff;";
+
+const N2T_HDL: &str = r"
+CHIP Mux16 {
+    IN a[16], b[16], sel;
+    OUT out[16];
+    PARTS:
+    Mux(a=a[0], b=b[0], sel=sel, out=out[0]);
+    Mux16(a=a[0..15], b=true, sel=false, out[2..4]=x, out[0]=out);
+}
+CHIP Bit {
+    IN in, load;
+    OUT out;
+    BUILTIN Bit; // comment
+    CLOCKED in, load;
+}";
+
+const SNIPPETS: &[&str] = &[
+    " ",
+    "\n",
+    "(",
+    ")",
+    "[",
+    "]",
+    "{",
+    "}",
+    ",",
+    ";",
+    ":",
+    "=",
+    "->",
+    "..",
+    ".",
+    "!",
+    "&",
+    "|",
+    "^",
+    "/*",
+    "*/",
+    "//",
+    "0",
+    "1",
+    "16",
+    "4096",
+    "4097",
+    "99999999999999999999",
+    "18446744073709551616",
+    "a",
+    "x",
+    "true",
+    "chip",
+    "component",
+    "CHIP",
+    "IN",
+    "OUT",
+    "PARTS:",
+    "BUILTIN",
+    "CLOCKED",
+    "in:",
+    "out:",
+    "parts:",
+    "func:",
+    "count:",
+    "fill1:",
+    "table",
+    "pin",
+    "dff",
+    ".count",
+    ".fill(1)",
+    "ä",
+    "\r\n",
+    "\r",
+];
+
+/// xorshift so the inputs are the same on every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn mutate(rng: &mut Rng, seed: &str) -> String {
+    let mut code = seed.as_bytes().to_vec();
+    for _ in 0..1 + rng.below(8) {
+        let at = rng.below(code.len() + 1);
+        match rng.below(4) {
+            0 if at < code.len() => {
+                let end = (at + rng.below(16)).min(code.len());
+                code.drain(at..end);
+            }
+            1 if at < code.len() => {
+                let end = (at + rng.below(32)).min(code.len());
+                let copy = code[at..end].to_vec();
+                code.splice(at..at, copy);
+            }
+            _ => {
+                let snippet = SNIPPETS[rng.below(SNIPPETS.len())];
+                code.splice(at..at, snippet.bytes());
+            }
+        }
+    }
+    String::from_utf8_lossy(&code).into_owned()
+}
+
+fn soup(rng: &mut Rng) -> String {
+    (0..rng.below(64))
+        .map(|_| SNIPPETS[rng.below(SNIPPETS.len())])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// the diagnostics of an error must not panic for any source
+fn render(err: hdl_parser::Error, code: &str) {
+    err.render("fuzz.hdl", code);
+    err.to_json("fuzz.hdl", code);
+}
+
+/// everything that parses has to give the same result after printing it
+fn parse_all(code: &str) {
+    match comphdl::parse(code) {
+        Ok(chips) => assert_eq!(comphdl::parse(&comphdl::to_string(&chips)), Ok(chips)),
+        Err(err) => render(err, code),
+    }
+    match shdl::parse(code) {
+        Ok(result) => assert_eq!(shdl::parse(&shdl::to_string(&result)), Ok(result)),
+        Err(err) => render(err, code),
+    }
+    match open_gal::parse(code) {
+        Ok(gal) => assert_eq!(open_gal::parse(&open_gal::to_string(&gal)), Ok(gal)),
+        Err(err) => render(err, code),
+    }
+    match n2t_hdl::parse(code) {
+        Ok(chips) => assert_eq!(n2t_hdl::parse(&n2t_hdl::to_string(&chips)), Ok(chips)),
+        Err(err) => render(err, code),
+    }
+}
+
+#[test]
+fn mutations() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for seed in [COMPHDL, SHDL, OPEN_GAL, N2T_HDL] {
+        parse_all(seed);
+        for _ in 0..2000 {
+            parse_all(&mutate(&mut rng, seed));
+        }
+    }
+}
+
+#[test]
+fn token_soup() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..5000 {
+        parse_all(&soup(&mut rng));
+    }
+}
+
+#[test]
+fn empty() {
+    for code in [
+        "",
+        " ",
+        "\n",
+        "// comment",
+        "/* block */",
+        "/* unterminated",
+        "CHIP Foo {\r",
+    ] {
+        parse_all(code);
+    }
+}
+
+#[test]
+fn huge_numbers() {
+    assert!(n2t_hdl::parse("CHIP A { IN a[99999999]; OUT b; PARTS: }").is_err());
+    assert!(n2t_hdl::parse("CHIP A { IN a[0..18446744073709551615]; OUT b; PARTS: }").is_err());
+    assert!(n2t_hdl::parse("CHIP A { IN a[99999999999999999999]; OUT b; PARTS: }").is_err());
+    assert!(comphdl::parse("component A(a[99999999:0]) -> b {}").is_err());
+    assert!(open_gal::parse("pin [1..99999999] = a;").is_err());
+    assert!(open_gal::parse("pin 99999999999999999999 = a;").is_err());
+    assert!(shdl::parse("chip A { in: a[99999999]; out: b; parts: }").is_err());
+}
+
+#[test]
+fn deep_nesting() {
+    let depth = 100_000;
+    let nested = format!("a = {}b{};", "(".repeat(depth), ")".repeat(depth));
+    assert!(open_gal::parse(&nested).is_err());
+    let not = format!("a = {}b;", "!".repeat(depth));
+    assert!(open_gal::parse(&not).is_err());
+    // long chains are flat and have to work
+    let chain = format!("a = b{};", " & b".repeat(depth));
+    assert!(open_gal::parse(&chain).is_ok());
+
+    let func = format!(
+        "chip A {{ in: b; out: a; func: a = {}b{}; }}",
+        "(".repeat(depth),
+        ")".repeat(depth)
+    );
+    assert!(shdl::parse(&func).is_err());
+    let chain = format!(
+        "chip A {{ in: b; out: a; func: a = b{}; }}",
+        " | b".repeat(depth)
+    );
+    assert!(shdl::parse(&chain).is_ok());
+}
+
+#[test]
+fn large_tables() {
+    let inputs = (0..64).map(|i| format!("i{}", i)).collect::<Vec<_>>();
+    let func = format!("a = {};", inputs.join(" & "));
+    assert!(open_gal::parse(&func).is_err());
+
+    let table = format!("table({} -> a).fill(1) {{ }}", inputs.join(", "));
+    assert!(open_gal::parse(&table).is_err());
+    let table = format!("table({} -> a).count {{ 01 }}", inputs.join(", "));
+    assert!(open_gal::parse(&table).is_err());
+
+    let chip = format!("chip A {{ in: {}; out: a; fill0: }}", inputs.join(", "));
+    assert!(shdl::parse(&chip).is_err());
+}
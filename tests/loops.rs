@@ -0,0 +1,151 @@
+use hdl_parser::{builtin, n2t_hdl, shdl, ErrorKind, Library, Project, Signature};
+
+fn library() -> Library {
+    vec![
+        Signature::new("Nand", vec!["a", "b"], vec!["out"]),
+        Signature::new("Not", vec!["in"], vec!["out"]),
+        Signature::new("Mux", vec!["a", "b", "sel"], vec!["out"]),
+        Signature::new("DFF", vec!["in"], vec!["out"]).with_clocked(&["in"]),
+        Signature::new("RAM8", vec!["in", "load", "address"], vec!["out"])
+            .with_clocked(&["in", "load"]),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn messages(errors: &[hdl_parser::Error]) -> Vec<&str> {
+    errors.iter().map(|err| err.message()).collect()
+}
+
+#[test]
+fn latch() {
+    let code = r"
+    chip RS_FF {
+        in: r, s;
+        out: q, q_n;
+        parts:
+            q = Nand(s, q_n);
+            q_n = Nand(r, q);
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+    let errors = chips[0].check_loops(&library(), &[]);
+
+    assert_eq!(
+        messages(&errors),
+        ["combinational loop <Nand -> q -> Nand -> q_n -> Nand>"]
+    );
+    assert_eq!(errors[0].kind(), &ErrorKind::CombinationalLoop);
+    assert_eq!(errors[0].span(), chips[0].parts()[0].span().range().into());
+    assert!(chips[0].check_loops(&library(), &["q"]).is_empty());
+}
+
+#[test]
+fn whole_buses() {
+    let code = r"
+    chip Test {
+        in: d[0..15], load, addr[0..2];
+        out: out[0..15];
+        parts:
+            out = RAM8(d, load, addr);
+            r = RAM8(r, load, addr);
+            q = RAM8(d, load, q[0..2]);
+    }";
+    let (chips, _) = shdl::parse(code).unwrap();
+
+    assert_eq!(
+        messages(&chips[0].check_loops(&builtin::library(), &[])),
+        ["combinational loop <RAM8 -> q[0] -> RAM8>"]
+    );
+}
+
+#[test]
+fn n2t_hdl() {
+    let code = r"
+    CHIP Test {
+        IN in, load;
+        OUT out, x;
+        PARTS:
+        Mux(a=dff, b=in, sel=load, out=m);
+        DFF(in=m, out=dff, out=out);
+        RAM8(in=r, load=load, address=r, out=r);
+        Not(in=y, out=x, out=z);
+        Nand(a=z, b=in, out=y);
+        Nand(a=w, b=w, out=w);
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+
+    assert_eq!(
+        messages(&chips[0].check_loops(&library(), &[])),
+        [
+            "combinational loop <RAM8 -> r -> RAM8>",
+            "combinational loop <Not -> z -> Nand -> y -> Not>",
+            "combinational loop <Nand -> w -> Nand>",
+        ]
+    );
+}
+
+#[test]
+fn project() {
+    let mut project = Project::new();
+    project.add(
+        "Bit.hdl",
+        "CHIP Bit {
+            IN in, load;
+            OUT out;
+            PARTS:
+            Mux(a=dff, b=in, sel=load, out=m);
+            DFF(in=m, out=dff, out=out);
+        }",
+    );
+    project.add(
+        "Counter.hdl",
+        "CHIP Counter {
+            IN load;
+            OUT out;
+            PARTS:
+            Not(in=q, out=n);
+            Bit(in=n, load=load, out=q, out=out);
+        }",
+    );
+    let library = project.resolve(&library()).unwrap();
+
+    assert!(library.get("Bit").unwrap().is_sequential());
+    assert!(library.get("Counter").unwrap().is_sequential());
+    for chip in project.definitions() {
+        assert_eq!(chip.check_loops(&library, &[]), []);
+    }
+}
+
+#[test]
+fn ram_address() {
+    let mut project = Project::new();
+    project.add(
+        "Mem.hdl",
+        "CHIP Mem {
+            IN in[16], load, address[3];
+            OUT out[16];
+            PARTS:
+            RAM8(in=in, load=load, address=address, out=out);
+        }",
+    );
+    project.add(
+        "Loop.hdl",
+        "CHIP Loop {
+            IN load;
+            OUT out[16];
+            PARTS:
+            Mem(in=o, load=load, address=a, out=o, out[0..2]=a, out=out);
+        }",
+    );
+    let library = project.resolve(&builtin::library()).unwrap();
+
+    // the address selects the output, so only the data goes through the clock
+    assert_eq!(library.get("Mem").unwrap().clocked(), ["in", "load"]);
+    let errors = project.get("Loop").unwrap().check_loops(&library, &[]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), &ErrorKind::CombinationalLoop);
+    assert_eq!(
+        errors[0].message(),
+        "combinational loop <Mem -> a[0] -> Mem>"
+    );
+}
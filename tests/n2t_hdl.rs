@@ -1,4 +1,4 @@
-use hdl_parser::{n2t_hdl, Chip, ComponentMap};
+use hdl_parser::{n2t_hdl, Chip, ComponentMap, ErrorKind, Signal};
 
 #[test]
 fn and() {
@@ -97,9 +97,18 @@ fn xor() {
             vec!["out"],
             vec![
                 ComponentMap::new(vec![("a", "a"), ("b", "b"), ("out", "ab_nand")], "Nand"),
-                ComponentMap::new(vec![("a", "a"), ("b", "ab_nand"), ("out", "a_nand")], "Nand"),
-                ComponentMap::new(vec![("a", "b"), ("b", "ab_nand"), ("out", "b_nand")], "Nand"),
-                ComponentMap::new(vec![("a", "a_nand"), ("b", "b_nand"), ("out", "out")], "Nand"),
+                ComponentMap::new(
+                    vec![("a", "a"), ("b", "ab_nand"), ("out", "a_nand")],
+                    "Nand"
+                ),
+                ComponentMap::new(
+                    vec![("a", "b"), ("b", "ab_nand"), ("out", "b_nand")],
+                    "Nand"
+                ),
+                ComponentMap::new(
+                    vec![("a", "a_nand"), ("b", "b_nand"), ("out", "out")],
+                    "Nand"
+                ),
             ]
         )])
     );
@@ -199,3 +208,321 @@ fn d_ff() {
         )])
     );
 }
+
+#[test]
+fn comments() {
+    let code = r"
+    // This file is part of www.nand2tetris.org
+    // and the book 'The Elements of Computing Systems'
+    // by Nisan and Schocken, MIT Press.
+    // File name: projects/01/Not.hdl
+
+    /**
+     * Not gate:
+     * out = not in
+     */
+
+    CHIP Not {
+        IN in;
+        OUT out;
+
+        PARTS:
+        // Put your code here:
+        Nand(a=in, b=in, out=out); /* same as Nand(a=in, b=true) */
+    }";
+
+    assert_eq!(
+        n2t_hdl::parse(code),
+        Ok(vec![Chip::new(
+            "Not",
+            vec!["in"],
+            vec!["out"],
+            vec![ComponentMap::new(
+                vec![("a", "in"), ("b", "in"), ("out", "out")],
+                "Nand"
+            )]
+        )])
+    );
+}
+
+#[test]
+fn bus() {
+    let code = r"
+    CHIP Test {
+        IN a[2], sel;
+        OUT out[2], msb;
+     PARTS:
+        Mux2(a=a, b=false, sel=sel, out=out, out[1]=msb, out[0..1]=x);
+        Or(a=x[0], b=true, out=y);
+    }";
+
+    assert_eq!(
+        n2t_hdl::parse(code),
+        Ok(vec![Chip::new_signals(
+            "Test",
+            vec![Signal::bus("a", 2), Signal::new("sel")],
+            vec![Signal::bus("out", 2), Signal::new("msb")],
+            vec![
+                ComponentMap::new_signals(
+                    vec![
+                        (Signal::new("a"), Signal::new("a")),
+                        (Signal::new("b"), Signal::new("false")),
+                        (Signal::new("sel"), Signal::new("sel")),
+                        (Signal::new("out"), Signal::new("out")),
+                        (Signal::index("out", 1), Signal::new("msb")),
+                        (Signal::slice("out", 0, 1), Signal::new("x")),
+                    ],
+                    "Mux2"
+                ),
+                ComponentMap::new_signals(
+                    vec![
+                        (Signal::new("a"), Signal::index("x", 0)),
+                        (Signal::new("b"), Signal::new("true")),
+                        (Signal::new("out"), Signal::new("y")),
+                    ],
+                    "Or"
+                ),
+            ]
+        )])
+    );
+}
+
+#[test]
+fn bus_width_mismatch() {
+    let code = r"
+    CHIP Test {
+        IN a[4];
+        OUT out;
+     PARTS:
+        Or4Way(in[0..1]=a, out=out);
+    }";
+
+    assert!(n2t_hdl::parse(code).is_err());
+}
+
+#[test]
+fn alu() {
+    let code = r"
+    // File name: projects/02/ALU.hdl
+
+    CHIP ALU {
+        IN
+            x[16], y[16],  // 16-bit inputs
+            zx, // zero the x input?
+            nx, // negate the x input?
+            zy, // zero the y input?
+            ny, // negate the y input?
+            f,  // compute out = x + y (if 1) or x & y (if 0)
+            no; // negate the out output?
+
+        OUT
+            out[16], // 16-bit output
+            zr, // 1 if (out == 0), 0 otherwise
+            ng; // 1 if (out < 0),  0 otherwise
+
+        PARTS:
+        Mux16(a=x, b=false, sel=zx, out=x1);
+        Not16(in=x1, out=notx1);
+        Mux16(a=x1, b=notx1, sel=nx, out=x2);
+
+        Mux16(a=y, b=false, sel=zy, out=y1);
+        Not16(in=y1, out=noty1);
+        Mux16(a=y1, b=noty1, sel=ny, out=y2);
+
+        And16(a=x2, b=y2, out=andxy);
+        Add16(a=x2, b=y2, out=addxy);
+        Mux16(a=andxy, b=addxy, sel=f, out=fxy);
+
+        Not16(in=fxy, out=notfxy);
+        Mux16(a=fxy, b=notfxy, sel=no, out=out, out[15]=ng, out[0..7]=low, out[8..15]=high);
+
+        Or8Way(in=low, out=zrlow);
+        Or8Way(in=high, out=zrhigh);
+        Or(a=zrlow, b=zrhigh, out=nzr);
+        Not(in=nzr, out=zr);
+    }";
+
+    assert!(n2t_hdl::parse(code).is_ok());
+}
+
+#[test]
+fn builtin() {
+    let code = r"
+    CHIP DFF {
+        IN  in;
+        OUT out;
+        BUILTIN DFF;
+        CLOCKED in;
+    }
+
+    CHIP RAM8 {
+        IN  in[2], load, address[3];
+        OUT out[2];
+        BUILTIN RAM8;
+        CLOCKED in, load;
+    }
+
+    CHIP Nand {
+        IN  a, b;
+        OUT out;
+        BUILTIN Nand;
+    }";
+
+    assert_eq!(
+        n2t_hdl::parse(code),
+        Ok(vec![
+            Chip::new_builtin("DFF", vec!["in"], vec!["out"], "DFF", vec!["in"]),
+            Chip::builder("RAM8")
+                .input_signal(Signal::bus("in", 2))
+                .input("load")
+                .input_signal(Signal::bus("address", 3))
+                .output_signal(Signal::bus("out", 2))
+                .builtin("RAM8")
+                .clocked(&["in", "load"])
+                .build()
+                .unwrap(),
+            Chip::new_builtin("Nand", vec!["a", "b"], vec!["out"], "Nand", Vec::new()),
+        ])
+    );
+}
+
+#[test]
+fn bit() {
+    let code = r"
+    CHIP Bit {
+        IN in, load;
+        OUT out;
+
+        PARTS:
+        Mux(a=dffout, b=in, sel=load, out=muxout);
+        DFF(in=muxout, out=dffout, out=out);
+    }";
+
+    assert_eq!(
+        n2t_hdl::parse(code),
+        Ok(vec![Chip::new(
+            "Bit",
+            vec!["in", "load"],
+            vec!["out"],
+            vec![
+                ComponentMap::new(
+                    vec![
+                        ("a", "dffout"),
+                        ("b", "in"),
+                        ("sel", "load"),
+                        ("out", "muxout")
+                    ],
+                    "Mux"
+                ),
+                ComponentMap::new(
+                    vec![("in", "muxout"), ("out", "dffout"), ("out", "out")],
+                    "DFF"
+                ),
+            ]
+        )])
+    );
+}
+
+#[test]
+fn invalid_character() {
+    let code = r"
+    CHIP Not {
+        IN in;
+        OUT out@;
+    }";
+
+    let err = n2t_hdl::parse(code).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidCharacter("@".to_string()));
+    assert_eq!(err.span(), Some(46..47));
+    assert_eq!(&code[46..47], "@");
+
+    let err = n2t_hdl::parse("CHIP Café {}").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidCharacter("é".to_string()));
+    assert_eq!(err.span(), Some(8..10));
+
+    let err = n2t_hdl::parse("CHIP Not { /* IN in; }").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidCharacter("/*".to_string()));
+}
+
+#[test]
+fn optional_sections() {
+    let code = r"
+    CHIP Computer {
+        IN reset;
+        PARTS:
+        ROM32K(address=pc, out=instruction);
+        CPU(inM=memOut, instruction=instruction, reset=reset, outM=outM,
+            writeM=writeM, addressM=addressM, pc=pc);
+        Memory(in=outM, load=writeM, address=addressM, out=memOut);
+    }
+
+    CHIP Empty {
+        PARTS:
+    }";
+
+    let chips = n2t_hdl::parse(code).unwrap();
+    assert_eq!(chips[0].inputs(), [Signal::new("reset")]);
+    assert!(chips[0].outputs().is_empty());
+    assert_eq!(chips[0].parts().len(), 3);
+    assert_eq!(
+        chips[1],
+        Chip::new("Empty", Vec::new(), Vec::new(), Vec::new())
+    );
+
+    let printed = n2t_hdl::to_string(&chips);
+    assert!(!printed.contains("OUT"));
+    assert_eq!(n2t_hdl::parse(&printed).unwrap(), chips);
+}
+
+#[test]
+fn builtin_io() {
+    let code = r"
+    /**
+     * The keyboard (memory map).
+     */
+    CHIP Keyboard {
+        OUT out[16];   // The ASCII code of the pressed key, or 0 if no key is pressed
+        BUILTIN Keyboard;
+    }
+
+    CHIP Screen {
+        IN  in[16],    // what to write
+        load,          // write-enable bit
+        address[13];   // where to write
+        OUT out[16];   // Screen value at the given address
+        BUILTIN Screen;
+        CLOCKED in, load;
+    }
+
+    CHIP Computer {
+        IN reset;
+        BUILTIN Computer;
+    }";
+
+    assert_eq!(
+        n2t_hdl::parse(code),
+        Ok(vec![
+            Chip::builder("Keyboard")
+                .output_signal(Signal::bus("out", 16))
+                .builtin("Keyboard")
+                .build()
+                .unwrap(),
+            Chip::builder("Screen")
+                .input_signal(Signal::bus("in", 16))
+                .input("load")
+                .input_signal(Signal::bus("address", 13))
+                .output_signal(Signal::bus("out", 16))
+                .builtin("Screen")
+                .clocked(&["in", "load"])
+                .build()
+                .unwrap(),
+            Chip::new_builtin(
+                "Computer",
+                vec!["reset"],
+                Vec::new(),
+                "Computer",
+                Vec::new()
+            ),
+        ])
+    );
+}
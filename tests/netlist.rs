@@ -0,0 +1,223 @@
+use hdl_parser::{
+    builtin, comphdl, n2t_hdl, open_gal, parse_any, shdl, Direction, Library, Netlist, Pin, Signal,
+};
+
+#[test]
+fn n2t_hdl() {
+    let code = r"
+    CHIP And {
+        IN a, b;
+        OUT out;
+        PARTS:
+        Nand(a=a, b=b, out=n);
+        Not(in=n, out=out);
+        Nand(a=true, b=a);
+    }";
+    let netlist = Netlist::from_n2t_hdl(&n2t_hdl::parse(code).unwrap(), &builtin::library());
+    let module = netlist.module("And").unwrap();
+
+    let ports: Vec<_> = module
+        .ports()
+        .iter()
+        .map(|port| (port.name(), port.direction()))
+        .collect();
+    assert_eq!(
+        ports,
+        [
+            ("a", Direction::Input),
+            ("b", Direction::Input),
+            ("out", Direction::Output)
+        ]
+    );
+    let nets: Vec<_> = module.nets().iter().map(|net| net.name()).collect();
+    assert_eq!(nets, ["a", "b", "out", "n", "true"]);
+    assert_eq!(
+        module.net(module.find_net("true").unwrap()).constant(),
+        Some(true)
+    );
+
+    let not = &module.instances()[1];
+    assert_eq!(not.module(), "Not");
+    assert_eq!(
+        not.connections(),
+        [
+            (
+                Pin::Named(Signal::new("in")),
+                vec![module.find_net("n").unwrap()]
+            ),
+            (
+                Pin::Named(Signal::new("out")),
+                vec![module.find_net("out").unwrap()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn shdl() {
+    let code = r"
+    chip A {
+        in: a, b;
+        out: x, y;
+        parts:
+            n = Xor(a, b);
+            x, y = n, a;
+    }
+    chip Xor {
+        in: a, b;
+        out: out;
+        full:
+            00 0
+            01 1
+            10 1
+            11 0
+    }";
+    let (chips, lut) = shdl::parse(code).unwrap();
+    let mut library = Library::new();
+    library.add_tables(&lut);
+    let netlist = Netlist::from_shdl(&(chips, lut), &library);
+    assert_eq!(netlist.modules().len(), 2);
+
+    let module = netlist.module("A").unwrap();
+    let n = module.find_net("n").unwrap();
+    assert_eq!(
+        module.instances()[0].connections(),
+        [
+            (Pin::Input(0), vec![module.find_net("a").unwrap()]),
+            (Pin::Input(1), vec![module.find_net("b").unwrap()]),
+            (Pin::Output(0), vec![n]),
+        ]
+    );
+    // every bit of the assignment is a buffer
+    assert_eq!(module.cells().len(), 2);
+    assert_eq!(module.cells()[0].inputs(), [n]);
+    assert_eq!(module.cells()[0].outputs(), [module.find_net("x").unwrap()]);
+    assert_eq!(module.cells()[0].table(), [vec![false, true]]);
+
+    let xor = netlist.module("Xor").unwrap();
+    let cell = &xor.cells()[0];
+    assert_eq!(cell.inputs().len(), 2);
+    assert_eq!(cell.table(), [vec![false, true, true, false]]);
+    assert_eq!(xor.outputs().count(), 1);
+}
+
+#[test]
+fn comphdl() {
+    let code = r"
+    component Half(a, b) -> (s, c) {
+        Xor(a, b) -> s;
+        And(a, b) -> c;
+    }";
+    let netlist = Netlist::from_comphdl(&comphdl::parse(code).unwrap(), &builtin::library());
+    let module = &netlist.modules()[0];
+
+    assert_eq!(module.name(), "Half");
+    assert_eq!(module.instances().len(), 2);
+    assert_eq!(module.instances()[1].module(), "And");
+    assert_eq!(
+        module.instances()[1].span(),
+        comphdl::parse(code).unwrap()[0].parts()[1].span()
+    );
+}
+
+#[test]
+fn open_gal() {
+    let code = r"
+    pin 1 = a;
+    pin 2 = b;
+    pin 13 = x;
+    table(a, b -> x) {
+        00 1
+        01 0
+        10 0
+        11 1
+    }
+    x.dff;";
+    let gal = open_gal::parse(code).unwrap();
+    let netlist = Netlist::from_open_gal(&gal, "gal");
+    let module = netlist.module("gal").unwrap();
+
+    let inputs: Vec<_> = module.inputs().map(|port| port.name()).collect();
+    let outputs: Vec<_> = module.outputs().map(|port| port.name()).collect();
+    assert_eq!(inputs, ["a", "b"]);
+    assert_eq!(outputs, ["x"]);
+
+    // the table drives the register and not the pin
+    let register = &module.registers()[0];
+    assert_eq!(module.net(register.d()).name(), "x.d");
+    assert_eq!(register.q(), module.find_net("x").unwrap());
+    assert_eq!(module.cells()[0].outputs(), [register.d()]);
+
+    let parse = parse_any(code).unwrap();
+    assert_eq!(Netlist::from_parse(&parse, &Library::new()), netlist);
+}
+
+#[test]
+fn buses() {
+    let code = r"
+    chip Not4 {
+        in: a[0..3];
+        out: x[0..3], y[0..3];
+        parts:
+            x = Not4(a);
+            y = x;
+    }";
+    let (chips, lut) = shdl::parse(code).unwrap();
+    let mut library = Library::new();
+    library.add_chips(&chips);
+    let netlist = Netlist::from_shdl(&(chips, lut), &library);
+    let module = netlist.module("Not4").unwrap();
+    let nets = |names: &[&str]| -> Vec<_> {
+        names
+            .iter()
+            .map(|name| module.find_net(name).unwrap())
+            .collect()
+    };
+
+    // a whole bus uses the nets of the declared pin
+    assert_eq!(
+        module.instances()[0].connections(),
+        [
+            (Pin::Input(0), nets(&["a[0]", "a[1]", "a[2]", "a[3]"])),
+            (Pin::Output(0), nets(&["x[0]", "x[1]", "x[2]", "x[3]"])),
+        ]
+    );
+    assert_eq!(module.cells().len(), 4);
+    assert_eq!(module.cells()[3].inputs(), nets(&["x[3]"]));
+    assert_eq!(module.cells()[3].outputs(), nets(&["y[3]"]));
+    assert!(module.find_net("a").is_none());
+
+    // an internal bus and a constant get the width of the whole pin
+    let code = r"
+    CHIP Zero {
+        IN a[16];
+        OUT out[16];
+        PARTS:
+        Not16(in=a, out=x);
+        And16(a=x, b=false, out=out);
+    }";
+    let netlist = Netlist::from_n2t_hdl(&n2t_hdl::parse(code).unwrap(), &builtin::library());
+    let module = netlist.module("Zero").unwrap();
+    let x: Vec<_> = (0..16)
+        .map(|i| module.find_net(&format!("x[{}]", i)).unwrap())
+        .collect();
+    let not = module.instances()[0].connections();
+    let and = module.instances()[1].connections();
+    assert_eq!(not[1].1, x);
+    assert_eq!(and[0].1, x);
+    assert_eq!(and[1].1, vec![module.find_net("false").unwrap(); 16]);
+    assert_eq!(and[2].1.len(), 16);
+    assert!(module.find_net("x").is_none());
+
+    let code = r"
+    component Zero(a[16]) -> out[16] {
+        Not16(a) -> x;
+        And16(x, false) -> out;
+    }";
+    let netlist = Netlist::from_comphdl(&comphdl::parse(code).unwrap(), &builtin::library());
+    let module = netlist.module("Zero").unwrap();
+    let and = module.instances()[1].connections();
+    assert_eq!(module.instances()[0].connections()[1].1, and[0].1);
+    assert_eq!(and[0].1.len(), 16);
+    assert_eq!(and[1].1, vec![module.find_net("false").unwrap(); 16]);
+}
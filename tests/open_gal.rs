@@ -1,4 +1,4 @@
-use hdl_parser::{open_gal, open_gal::OGalParse, LookupTable};
+use hdl_parser::{open_gal, open_gal::OGalParse, ErrorKind, LookupTable, Signal};
 
 #[test]
 fn full() {
@@ -15,7 +15,7 @@ fn full() {
         Ok(OGalParse::lut(vec![LookupTable::new(
             "",
             vec!["i0", "i1"],
-            vec!["and"],
+            vec!["and", "or"],
             vec![
                 vec![false, false, false, true],
                 vec![false, true, true, true]
@@ -80,14 +80,14 @@ fn easy_gal() {
                 LookupTable::new(
                     "",
                     vec!["i0", "i1"],
-                    vec!["or"],
-                    vec![vec![false, true, true, true]]
+                    vec!["xor"],
+                    vec![vec![false, true, true, false]]
                 ),
                 LookupTable::new(
                     "",
                     vec!["i0", "i1"],
-                    vec!["xor"],
-                    vec![vec![false, true, true, false]]
+                    vec!["or"],
+                    vec![vec![false, true, true, true]]
                 ),
                 LookupTable::new(
                     "",
@@ -125,14 +125,14 @@ fn open_gal() {
 
     assert_eq!(
         open_gal::parse(code),
-        Ok(OGalParse::new(
+        Ok(OGalParse::new_signals(
             vec![
-                ("i0", 1),
-                ("i1", 2),
-                ("and", 13),
-                ("or", 14),
-                ("xor", 15),
-                ("not", 16)
+                (Signal::index("i", 0), 1),
+                (Signal::index("i", 1), 2),
+                (Signal::new("and"), 13),
+                (Signal::new("or"), 14),
+                (Signal::new("xor"), 15),
+                (Signal::new("not"), 16)
             ],
             vec![
                 LookupTable::new(
@@ -153,9 +153,102 @@ fn open_gal() {
                     vec!["xor"],
                     vec![vec![false, true, true, false]]
                 ),
-                LookupTable::new("", vec!["i0", "i1"], vec!["not"], vec![vec![true, false]])
+                LookupTable::new("", vec!["i0"], vec!["not"], vec![vec![true, false]])
             ],
             Vec::new()
         ))
     );
 }
+
+#[test]
+fn bits() {
+    let code = r"
+    pin 1, 2 = i[0..1];
+    pin [13..14] = o[0..1];
+    table(i[0..1] -> o[0]).fill(0) {
+        11 1
+    }
+    o[1] = i[0] ^ i[1];
+    o[1].dff;";
+
+    assert_eq!(
+        open_gal::parse(code),
+        Ok(OGalParse::new_signals(
+            vec![
+                (Signal::index("i", 0), 1),
+                (Signal::index("i", 1), 2),
+                (Signal::index("o", 0), 13),
+                (Signal::index("o", 1), 14)
+            ],
+            vec![
+                LookupTable::new_signals(
+                    "",
+                    vec![Signal::bus("i", 2)],
+                    vec![Signal::index("o", 0)],
+                    vec![vec![false, false, false, true]]
+                ),
+                LookupTable::new_signals(
+                    "",
+                    vec![Signal::bus("i", 2)],
+                    vec![Signal::index("o", 1)],
+                    vec![vec![false, true, true, false]]
+                )
+            ],
+            vec![Signal::index("o", 1)]
+        ))
+    );
+}
+
+#[test]
+fn func() {
+    let code = r"
+    a = (!b | (c));
+    x = a ^ b & c;";
+
+    assert_eq!(
+        open_gal::parse(code),
+        Ok(OGalParse::lut(vec![
+            LookupTable::new(
+                "",
+                vec!["b", "c"],
+                vec!["a"],
+                vec![vec![true, true, false, true]]
+            ),
+            LookupTable::new(
+                "",
+                vec!["a", "b", "c"],
+                vec!["x"],
+                vec![vec![false, false, false, true, true, true, true, false]]
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn pin_count_mismatch() {
+    let code = r"
+    pin [1..3] = a, b;";
+
+    assert!(open_gal::parse(code).is_err());
+}
+
+#[test]
+fn invalid_character() {
+    let code = "pin 1 = a;\npin 2 = #b;";
+
+    let err = open_gal::parse(code).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidCharacter("#".to_string()));
+    assert_eq!(err.span(), Some(19..20));
+}
+
+#[test]
+fn reversed_range() {
+    let err = open_gal::parse("pin [16..13] = a, b, c, d;").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRange);
+    assert_eq!(err.message(), "invalid range <16..13>");
+    assert_eq!(err.span(), Some(9..11));
+
+    let err = open_gal::parse("pin 1, 2 = i[1..0];").unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::InvalidRange);
+    assert_eq!(err.span(), Some(16..17));
+}
@@ -0,0 +1,191 @@
+use hdl_parser::{comphdl, n2t_hdl, open_gal, shdl, LookupTable};
+
+#[test]
+fn n2t_hdl() {
+    let code = r"
+    CHIP Mux16 {
+        IN a[2], b[2], sel;
+        OUT out[2];
+        PARTS:
+        Mux(a=a[0], b=b[0], sel=sel, out=out[0]);
+        Mux(a=a[1], b=true, sel=sel, out=out[1]);
+    }
+    CHIP Bit {
+        IN in, load; OUT out;
+        BUILTIN Bit;
+        CLOCKED in, load;
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let printed = n2t_hdl::to_string(&chips);
+
+    assert_eq!(
+        printed,
+        "CHIP Mux16 {
+    IN a[2], b[2], sel;
+    OUT out[2];
+    PARTS:
+    Mux(a=a[0], b=b[0], sel=sel, out=out[0]);
+    Mux(a=a[1], b=true, sel=sel, out=out[1]);
+}
+
+CHIP Bit {
+    IN in, load;
+    OUT out;
+    BUILTIN Bit;
+    CLOCKED in, load;
+}
+"
+    );
+    assert_eq!(n2t_hdl::parse(&printed).unwrap(), chips);
+
+    // only a range from bit 0 can be written as a width
+    let code = "CHIP A {\n    IN a[3..3], b[2..4], c[0..3];\n    OUT out;\n    PARTS:\n}\n";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let printed = n2t_hdl::to_string(&chips);
+    assert_eq!(
+        printed,
+        "CHIP A {\n    IN a[3..3], b[2..4], c[4];\n    OUT out;\n    PARTS:\n}\n"
+    );
+    assert_eq!(n2t_hdl::parse(&printed).unwrap(), chips);
+}
+
+#[test]
+fn comphdl() {
+    let code = r"
+    component Bus(a[1:0], en) -> (b[0:1], c) {
+        Buf(a[1]) -> b[0];
+        Nop(en);
+        (b[1], c) = (a[0], en);
+        x = a[0];
+    }";
+    let chips = comphdl::parse(code).unwrap();
+    let printed = comphdl::to_string(&chips);
+
+    assert_eq!(
+        printed,
+        "component Bus(a[1:0], en) -> (b[0:1], c) {
+    Buf(a[1]) -> b[0];
+    Nop(en);
+    (b[1], c) = (a[0], en);
+    x = a[0];
+}
+"
+    );
+    assert_eq!(comphdl::parse(&printed).unwrap(), chips);
+}
+
+#[test]
+fn shdl() {
+    let code = r"
+    chip Half {
+        in: a, b;
+        out: s, c;
+        func:
+            s = a ^ b;
+            c = a & b;
+    }
+    chip And {
+        in: a, b;
+        out: x;
+        parts:
+            n = Nand(a, b);
+            x = n;
+    }
+    chip One {
+        in: a;
+        out: x;
+        count: 11
+    }";
+    let result = shdl::parse(code).unwrap();
+    let printed = shdl::to_string(&result);
+
+    assert_eq!(
+        printed,
+        "chip And {
+    in: a, b;
+    out: x;
+    parts:
+        n = Nand(a, b);
+        x = n;
+}
+
+chip Half {
+    in: a, b;
+    out: s, c;
+    full:
+        00 00
+        01 10
+        10 10
+        11 01
+}
+
+chip One {
+    in: a;
+    out: x;
+    full:
+        0 1
+        1 1
+}
+"
+    );
+    assert_eq!(shdl::parse(&printed).unwrap(), result);
+}
+
+#[test]
+fn open_gal() {
+    let code = r"
+    pin 1, 2 = i[0..1];
+    pin 23 = x;
+    table(i0, i1 -> x).fill(0) {
+        11 1
+    }
+    y = !i0 | i1;
+    x.dff;";
+    let gal = open_gal::parse(code).unwrap();
+    let printed = open_gal::to_string(&gal);
+
+    assert_eq!(
+        printed,
+        "pin 1 = i[0];
+pin 2 = i[1];
+pin 23 = x;
+
+table(i0, i1 -> x) {
+    00 0
+    01 0
+    10 0
+    11 1
+}
+
+table(i0, i1 -> y) {
+    00 1
+    01 1
+    10 0
+    11 1
+}
+
+x.dff;
+"
+    );
+    assert_eq!(open_gal::parse(&printed).unwrap(), gal);
+    assert_eq!(open_gal::to_string(&open_gal::parse("").unwrap()), "");
+}
+
+#[test]
+fn short_columns() {
+    // a table that was not checked by a parser prints its missing values as 0
+    let lookup_table = LookupTable::new("And", vec!["a", "b"], vec!["x"], vec![vec![true]]);
+    assert_eq!(
+        shdl::to_string(&(vec![], vec![lookup_table])),
+        "chip And {
+    in: a, b;
+    out: x;
+    full:
+        00 1
+        01 0
+        10 0
+        11 0
+}
+"
+    );
+}
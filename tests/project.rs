@@ -0,0 +1,113 @@
+use hdl_parser::{ErrorKind, Library, Project, Signature};
+use std::fs;
+
+fn builtins() -> Library {
+    vec![Signature::new("Nand", vec!["a", "b"], vec!["out"])]
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn resolve() {
+    let mut project = Project::new();
+    project.add(
+        "Not.hdl",
+        "CHIP Not { IN in; OUT out; PARTS: Nand(a=in, b=in, out=out); }",
+    );
+    project.add(
+        "and.shdl",
+        "chip And { in: a, b; out: x; parts: n = Nand(a, b); x = Not(n); }",
+    );
+    project.add(
+        "or.comphdl",
+        "component Or(a, b) -> x { Not(a) -> na; Not(b) -> nb; Nand(na, nb) -> x; }",
+    );
+
+    assert!(project.errors().is_empty());
+    let names: Vec<_> = project.definitions().map(|chip| chip.name()).collect();
+    assert_eq!(names, ["And", "Not", "Or"]);
+    assert_eq!(
+        project.sources().name(project.get("Or").unwrap().file()),
+        Some("or.comphdl")
+    );
+
+    let library = project.resolve(&builtins()).unwrap();
+    let names: Vec<_> = library
+        .signatures()
+        .map(|signature| signature.name())
+        .collect();
+    assert_eq!(names, ["And", "Nand", "Not", "Or"]);
+}
+
+#[test]
+fn errors() {
+    let mut project = Project::new();
+    project.add(
+        "a.hdl",
+        "CHIP A { IN a; OUT out; PARTS: B(in=a, out=out); }",
+    );
+    let b = project.add(
+        "b.hdl",
+        "CHIP B { IN in; OUT out; PARTS: A(a=in, out=x); Xor(a=x, b=in, out=out); }",
+    );
+    let dup = project.add(
+        "dup.hdl",
+        "CHIP A { IN a; OUT out; PARTS: Nand(a=a, b=a, out=out); }",
+    );
+    project.add("bad.hdl", "CHIP C { IN a }");
+
+    assert_eq!(project.errors().len(), 2);
+    let errors = project.resolve(&builtins()).unwrap_err();
+    let kinds: Vec<_> = errors.iter().map(|err| err.kind().clone()).collect();
+    assert_eq!(
+        kinds,
+        [
+            ErrorKind::CircularDependency,
+            ErrorKind::UnknownChip,
+            ErrorKind::DuplicateChip,
+            ErrorKind::UnexpectedToken
+        ]
+    );
+
+    assert_eq!(errors[0].file(), b);
+    assert_eq!(errors[0].message(), "circular dependency <A -> B -> A>");
+    assert_eq!(errors[1].message(), "unknown chip <Xor>");
+    assert_eq!(errors[2].file(), dup);
+    assert_eq!(
+        errors[2].message(),
+        "chip <A> is already defined in <a.hdl>"
+    );
+    assert_eq!(
+        project.sources().render(&errors[1]).unwrap(),
+        "error: unknown chip <Xor>
+ --> b.hdl:1:49
+  |
+1 | CHIP B { IN in; OUT out; PARTS: A(a=in, out=x); Xor(a=x, b=in, out=out); }
+  |                                                 ^^^^^^^^^^^^^^^^^^^^^^^^
+"
+    );
+}
+
+#[test]
+fn load_dir() {
+    let dir = std::env::temp_dir().join(format!("hdl_parser_project_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("Not.hdl"),
+        "CHIP Not { IN in; OUT out; PARTS: Nand(a=in, b=in, out=out); }",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("Id.comphdl"),
+        "component Id(a) -> x { Not(a) -> n; Not(n) -> x; }",
+    )
+    .unwrap();
+    fs::write(dir.join("notes.txt"), "not a chip").unwrap();
+
+    let project = Project::load_dir(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<_> = project.definitions().map(|chip| chip.name()).collect();
+    assert_eq!(names, ["Id", "Not"]);
+    assert!(project.resolve(&builtins()).is_ok());
+}
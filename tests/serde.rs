@@ -0,0 +1,110 @@
+#![cfg(feature = "serde")]
+
+use hdl_parser::{comphdl, n2t_hdl, open_gal, shdl, Chip, ComponentMap, Error};
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn n2t_hdl() {
+    let code = r"
+    CHIP And {
+        IN a, b;
+        OUT out;
+        PARTS:
+        Nand(a=a, b=b, out=n);
+        Not(in=n, out=out);
+    }";
+    let chips = n2t_hdl::parse(code).unwrap();
+    let result = round_trip(&chips);
+
+    assert_eq!(result, chips);
+    assert_eq!(result[0].span(), chips[0].span());
+    assert_eq!(result[0].parts()[1].span(), chips[0].parts()[1].span());
+}
+
+#[test]
+fn comphdl() {
+    let code = r"
+    component And(a, b) -> x {
+        Nand(a, b) -> n;
+        Nand(n, n) -> x;
+    }";
+    let chips = comphdl::parse(code).unwrap();
+    assert_eq!(round_trip(&chips), chips);
+}
+
+#[test]
+fn shdl() {
+    let code = r"
+    chip Half {
+        in: a, b;
+        out: s, c;
+        func:
+            s = a ^ b;
+            c = a & b;
+    }
+    chip And {
+        in: a, b;
+        out: x;
+        parts:
+            n = Nand(a, b);
+            x = Not(n);
+    }";
+    let result = shdl::parse(code).unwrap();
+    assert_eq!(round_trip(&result), result);
+}
+
+#[test]
+fn open_gal() {
+    let gal = open_gal::parse("pin 1, 2, 23 = a, b, x; x = a & b; x.dff;").unwrap();
+    let result = round_trip(&gal);
+
+    assert_eq!(result, gal);
+    assert_eq!(result.pin_spans(), gal.pin_spans());
+}
+
+#[test]
+fn error() {
+    let err = n2t_hdl::parse("CHIP Not {\n    IN in;\n    OUT out@;\n}").unwrap_err();
+    let result: Error = round_trip(&err);
+
+    assert_eq!(result, err);
+    assert_eq!(result.span(), err.span());
+}
+
+#[test]
+fn without_spans() {
+    let json = r#"{
+        "name": "Not",
+        "inputs": ["in"],
+        "outputs": ["out"],
+        "parts": [{"var_map": [["a", "in"], ["b", "in"], ["out", "out"]], "name": "Nand"}],
+        "builtin": null,
+        "clocked": []
+    }"#;
+    let chip: Chip<ComponentMap> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(
+        chip,
+        Chip::new(
+            "Not",
+            vec!["in"],
+            vec!["out"],
+            vec![ComponentMap::new(
+                vec![("a", "in"), ("b", "in"), ("out", "out")],
+                "Nand"
+            )]
+        )
+    );
+
+    let gal: open_gal::OGalParse =
+        serde_json::from_str(r#"{"pin_map": [["a", 1]], "lookup_table": [], "dff_enable": []}"#)
+            .unwrap();
+    assert_eq!(gal.pins().count(), 1);
+}
@@ -1,4 +1,4 @@
-use hdl_parser::{shdl, Chip, ComponentIO, LookupTable};
+use hdl_parser::{shdl, Chip, ComponentIO, LookupTable, Signal};
 
 #[test]
 fn and_from_nand() {
@@ -173,7 +173,7 @@ fn rs_ff() {
                 vec!["q", "q_n"],
                 vec![
                     ComponentIO::new(vec!["s", "q_n"], vec!["q"], "Nand"),
-                    ComponentIO::new(vec!["s", "q_n"], vec!["q"], "Nand")
+                    ComponentIO::new(vec!["r", "q"], vec!["q_n"], "Nand")
                 ]
             )],
             Vec::new()
@@ -231,26 +231,109 @@ fn test() {
     assert_eq!(
         shdl::parse(code),
         Ok((
-            vec![Chip::new(
-                "JK_FF",
-                vec!["a2", "a3", "a4", "a5", "b", "c1"],
-                vec!["out0", "out1", "out2", "out3", "out4", "out5"],
+            vec![Chip::new_signals(
+                "Test",
                 vec![
-                    ComponentIO::new(
-                        vec!["a5", "b", "c1"],
-                        vec!["x0", "x1", "x2", "x3", "x4"],
+                    Signal::slice("a", 2, 5),
+                    Signal::new("b"),
+                    Signal::new("c1")
+                ],
+                vec![Signal::new("out0"), Signal::slice("out", 1, 4)],
+                vec![
+                    ComponentIO::new_signals(
+                        vec![Signal::new("a5"), Signal::new("b"), Signal::new("c1")],
+                        vec![Signal::bus("x", 4)],
                         "A"
                     ),
-                    ComponentIO::new(vec!["a2", "a3", "a4"], vec!["out0", "out1", "out2", "out3"], "B"),
-                    ComponentIO::new(
-                        vec!["x0", "x1", "x2", "x3", "x4"],
-                        vec!["out0", "out1", "out2", "out3", "out4"],
+                    ComponentIO::new_signals(
+                        vec![Signal::slice("a", 2, 4)],
+                        vec![Signal::new("out2"), Signal::new("out4")],
+                        "B"
+                    ),
+                    ComponentIO::new_signals(
+                        vec![Signal::new("x0"), Signal::slice("x", 1, 3)],
+                        vec![Signal::bus("out", 4)],
+                        ""
+                    ),
+                    ComponentIO::new_signals(
+                        vec![Signal::new("c1")],
+                        vec![Signal::new("out4")],
                         ""
                     ),
-                    ComponentIO::new(vec!["out4"], vec!["c1"], ""),
                 ]
             )],
             Vec::new()
         ))
     );
 }
+
+#[test]
+fn half_adder_func() {
+    let code = r"
+    chip HalfAdder {
+        in: a, b;
+        out: sum, carry;
+        func:
+            carry = a & b;
+            sum = a & !b | !a & b;
+    }";
+
+    assert_eq!(
+        shdl::parse(code),
+        Ok((
+            Vec::new(),
+            vec![LookupTable::new(
+                "HalfAdder",
+                vec!["a", "b"],
+                vec!["sum", "carry"],
+                vec![
+                    vec![false, true, true, false],
+                    vec![false, false, false, true]
+                ]
+            )]
+        ))
+    );
+}
+
+#[test]
+fn full_missing_row() {
+    let code = r"
+    chip And {
+        in: a, b;
+        out: out;
+        full:
+            00 0
+            01 0
+            11 1
+    }";
+
+    assert!(shdl::parse(code).is_err());
+}
+
+#[test]
+fn func_bits() {
+    let code = r"
+    chip Swap {
+        in: a[0..1];
+        out: y[0..1];
+        func:
+            y[0] = !a[1];
+            y[1] = a[0] ^ a[1];
+    }";
+
+    assert_eq!(
+        shdl::parse(code),
+        Ok((
+            Vec::new(),
+            vec![LookupTable::new_signals(
+                "Swap",
+                vec![Signal::bus("a", 2)],
+                vec![Signal::bus("y", 2)],
+                vec![
+                    vec![true, false, true, false],
+                    vec![false, true, true, false]
+                ]
+            )]
+        ))
+    );
+}
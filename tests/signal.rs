@@ -0,0 +1,85 @@
+use hdl_parser::{comphdl, n2t_hdl, Chip, ComponentIO, Library, Signal, Signature};
+
+#[test]
+fn bits() {
+    let bus = Signal::slice("a", 3, 0);
+    assert_eq!(bus.width(), 4);
+    assert_eq!(bus.to_string(), "a[3..0]");
+    assert_eq!(
+        bus.bits(),
+        [
+            Signal::index("a", 3),
+            Signal::index("a", 2),
+            Signal::index("a", 1),
+            Signal::index("a", 0)
+        ]
+    );
+
+    assert_eq!(Signal::bus("a", 16).range(), Some((0, 15)));
+    assert_eq!(Signal::index("a", 2).to_string(), "a[2]");
+    assert_eq!(Signal::new("a").bits(), [Signal::new("a")]);
+    assert_eq!(Signal::new("a"), "a");
+    assert_ne!(Signal::index("a", 0), "a");
+}
+
+#[test]
+fn no_collision() {
+    let code = r"
+    CHIP A {
+        IN in[3], in2;
+        OUT out;
+        PARTS:
+        And(a=in[2], b=in2, out=out);
+    }";
+    let chip = &n2t_hdl::parse(code).unwrap()[0];
+
+    assert_eq!(chip.width("in"), Some(3));
+    assert_eq!(chip.width("in2"), Some(1));
+    assert_eq!(chip.bits().len(), 5);
+    assert_eq!(
+        chip.parts()[0].var_map()[..2],
+        [
+            (Signal::new("a"), Signal::index("in", 2)),
+            (Signal::new("b"), Signal::new("in2"))
+        ]
+    );
+}
+
+#[test]
+fn convert() {
+    let library: Library = vec![Signature::new_signals(
+        "Not4",
+        vec![Signal::bus("in", 4)],
+        vec![Signal::bus("out", 4)],
+    )]
+    .into_iter()
+    .collect();
+    let code = r"
+    CHIP A {
+        IN a[4];
+        OUT out[4];
+        PARTS:
+        Not4(in=a, out=out);
+    }";
+    let chip = &n2t_hdl::parse(code).unwrap()[0];
+    let converted = chip.to_component_io(&library).unwrap();
+
+    assert_eq!(
+        converted,
+        Chip::new_signals(
+            "A",
+            vec![Signal::bus("a", 4)],
+            vec![Signal::bus("out", 4)],
+            vec![ComponentIO::new_signals(
+                vec![Signal::bus("a", 4)],
+                vec![Signal::bus("out", 4)],
+                "Not4"
+            )]
+        )
+    );
+    assert_eq!(
+        comphdl::to_string(std::slice::from_ref(&converted)),
+        "component A(a[0:3]) -> out[0:3] {\n    Not4(a[0:3]) -> out[0:3];\n}\n"
+    );
+    assert_eq!(&converted.to_component_map(&library).unwrap(), chip);
+}
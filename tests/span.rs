@@ -0,0 +1,50 @@
+use hdl_parser::{comphdl, n2t_hdl, open_gal, shdl, SourceMap, Span};
+
+#[test]
+fn n2t_hdl() {
+    let code =
+        "CHIP Not {\n    IN in;\n    OUT out;\n    PARTS:\n    Nand(a=in, b=in, out=out);\n}\n";
+    let mut sources = SourceMap::new();
+    sources.add("and.hdl", "");
+    let file = sources.add("not.hdl", code);
+
+    let chips = n2t_hdl::parse_file(file, code).unwrap();
+    let span = chips[0].span();
+    assert_eq!(span, Span::new(file, 0, code.len() - 1));
+    assert_eq!(sources.name(span.file), Some("not.hdl"));
+    assert_eq!(sources.location(span), Some((1, 1)));
+
+    let span = Span::new(file, 50, 76);
+    assert_eq!(sources.snippet(span), Some("Nand(a=in, b=in, out=out);"));
+    assert_eq!(sources.location(span), Some((5, 5)));
+}
+
+#[test]
+fn comphdl() {
+    let code = "component Not(a) -> x {\n    x = a;\n}";
+    let chips = comphdl::parse(code).unwrap();
+    assert_eq!(chips[0].span().range(), 0..code.len());
+}
+
+#[test]
+fn shdl() {
+    let code = "chip Not { in: a; out: x; full: 01 10 }\nchip Id { in: a; out: x; parts: x = a; }";
+    let (chips, lut) = shdl::parse(code).unwrap();
+    assert_eq!(lut[0].span().range(), 0..39);
+    assert_eq!(chips[0].span().range(), 40..code.len());
+}
+
+#[test]
+fn open_gal() {
+    let code = "pin 1, 2 = a, b;\ntable(a -> b) {\n    01\n    10\n}\nb = !a;";
+    let mut sources = SourceMap::new();
+    let file = sources.add("gal.txt", code);
+
+    let gal = open_gal::parse_file(file, code).unwrap();
+    assert_eq!(
+        gal.pin_spans(),
+        &[Span::new(file, 0, 16), Span::new(file, 0, 16)]
+    );
+    assert_eq!(sources.location(Span::new(file, 49, 56)), Some((6, 1)));
+    assert_eq!(sources.snippet(Span::new(file, 49, 56)), Some("b = !a;"));
+}